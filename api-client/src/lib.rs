@@ -14,11 +14,20 @@ use shuttle_common::models::{
     deployment::{
         DeploymentListResponse, DeploymentRequest, DeploymentResponse, UploadArchiveResponse,
     },
+    github::{
+        CreateDeploymentFromGithubRequest, GetGithubRepoBranchesResponse,
+        GithubInstallationsResponse, GithubRepoLink, LinkGithubRepoRequest,
+        UpdateGithubRepoBranchRequest,
+    },
     log::LogsResponse,
-    project::{ProjectCreateRequest, ProjectListResponse, ProjectResponse, ProjectUpdateRequest},
+    project::{
+        ProjectCreateRequest, ProjectLimitsResponse, ProjectListResponse, ProjectResponse,
+        ProjectScaleRequest, ProjectUpdateRequest,
+    },
     resource::{ProvisionResourceRequest, ResourceListResponse, ResourceResponse, ResourceType},
-    team::TeamListResponse,
-    user::UserResponse,
+    team::{AddTeamMemberRequest, TeamListResponse, TeamMembersResponse},
+    telemetry::{TelemetryConfigResponse, TelemetrySinkConfig, TelemetrySinkConfigDiscriminants},
+    user::{AccountLimitsResponse, UserResponse, UserUsageResponse},
 };
 use tokio::net::TcpStream;
 use tokio_tungstenite::{
@@ -120,6 +129,14 @@ impl ShuttleApiClient {
         self.get_json("/users/me").await
     }
 
+    pub async fn get_user_usage(&self) -> Result<ParsedJson<UserUsageResponse>> {
+        self.get_json("/users/me/usage").await
+    }
+
+    pub async fn get_account_limits(&self) -> Result<ParsedJson<AccountLimitsResponse>> {
+        self.get_json("/users/me/limits").await
+    }
+
     pub async fn deploy(
         &self,
         project: &str,
@@ -262,6 +279,13 @@ impl ShuttleApiClient {
         self.get_json(format!("/projects/{project}")).await
     }
 
+    pub async fn get_project_limits(
+        &self,
+        project: &str,
+    ) -> Result<ParsedJson<ProjectLimitsResponse>> {
+        self.get_json(format!("/projects/{project}/limits")).await
+    }
+
     pub async fn get_projects_list(&self) -> Result<ParsedJson<ProjectListResponse>> {
         self.get_json("/projects".to_owned()).await
     }
@@ -275,15 +299,125 @@ impl ShuttleApiClient {
             .await
     }
 
+    pub async fn scale_project(
+        &self,
+        project: &str,
+        req: ProjectScaleRequest,
+    ) -> Result<ParsedJson<ProjectResponse>> {
+        self.put_json(format!("/projects/{project}/scale"), Some(req))
+            .await
+    }
+
     pub async fn delete_project(&self, project: &str) -> Result<ParsedJson<String>> {
         self.delete_json(format!("/projects/{project}")).await
     }
 
-    #[allow(unused)]
-    async fn get_teams_list(&self) -> Result<ParsedJson<TeamListResponse>> {
+    pub async fn get_github_installations(
+        &self,
+    ) -> Result<ParsedJson<GithubInstallationsResponse>> {
+        self.get_json("/github/installations").await
+    }
+
+    pub async fn link_github_repo(
+        &self,
+        project: &str,
+        req: LinkGithubRepoRequest,
+    ) -> Result<ParsedJson<GithubRepoLink>> {
+        self.post_json(format!("/projects/{project}/repo"), Some(req))
+            .await
+    }
+
+    pub async fn unlink_github_repo(&self, project: &str) -> Result<ParsedJson<String>> {
+        self.delete_json(format!("/projects/{project}/repo")).await
+    }
+
+    pub async fn get_github_repo_branches(
+        &self,
+        project: &str,
+    ) -> Result<ParsedJson<GetGithubRepoBranchesResponse>> {
+        self.get_json(format!("/projects/{project}/repo/branches"))
+            .await
+    }
+
+    pub async fn update_github_repo_branch(
+        &self,
+        project: &str,
+        branch: String,
+    ) -> Result<ParsedJson<GithubRepoLink>> {
+        self.put_json(
+            format!("/projects/{project}/repo/branch"),
+            Some(UpdateGithubRepoBranchRequest { branch }),
+        )
+        .await
+    }
+
+    pub async fn deploy_from_github(
+        &self,
+        project: &str,
+        branch: String,
+    ) -> Result<ParsedJson<DeploymentResponse>> {
+        self.post_json(
+            format!("/projects/{project}/deployments/github"),
+            Some(CreateDeploymentFromGithubRequest { branch }),
+        )
+        .await
+    }
+
+    pub async fn get_telemetry_config(
+        &self,
+        project: &str,
+    ) -> Result<ParsedJson<TelemetryConfigResponse>> {
+        self.get_json(format!("/projects/{project}/telemetry/config"))
+            .await
+    }
+
+    pub async fn set_telemetry_sink(
+        &self,
+        project: &str,
+        config: &TelemetrySinkConfig,
+    ) -> Result<ParsedJson<TelemetryConfigResponse>> {
+        self.post_json(format!("/projects/{project}/telemetry/sinks"), Some(config))
+            .await
+    }
+
+    pub async fn delete_telemetry_sink(
+        &self,
+        project: &str,
+        sink: TelemetrySinkConfigDiscriminants,
+    ) -> Result<ParsedJson<TelemetryConfigResponse>> {
+        self.delete_json(format!(
+            "/projects/{project}/telemetry/sinks/{}",
+            sink.as_ref()
+        ))
+        .await
+    }
+
+    pub async fn get_teams_list(&self) -> Result<ParsedJson<TeamListResponse>> {
         self.get_json("/teams").await
     }
 
+    pub async fn get_team_members(&self, team: &str) -> Result<ParsedJson<TeamMembersResponse>> {
+        self.get_json(format!("/teams/{team}/members")).await
+    }
+
+    pub async fn add_team_member(
+        &self,
+        team: &str,
+        req: AddTeamMemberRequest,
+    ) -> Result<ParsedJson<String>> {
+        self.post_json(format!("/teams/{team}/members"), Some(req))
+            .await
+    }
+
+    pub async fn remove_team_member(
+        &self,
+        team: &str,
+        user_id: &str,
+    ) -> Result<ParsedJson<String>> {
+        self.delete_json(format!("/teams/{team}/members/{user_id}"))
+            .await
+    }
+
     pub async fn get_deployment_logs(
         &self,
         project: &str,
@@ -1,5 +1,5 @@
 use askama::Template;
-use shuttle_common::models::deployment::BuildArgsRust;
+use shuttle_common::models::deployment::{BuildArgsRust, RuntimeImage};
 
 #[derive(Template)]
 #[template(path = "rust.Dockerfile.jinja2", escape = "none")]
@@ -13,15 +13,45 @@ pub struct RustDockerfile<'a> {
     /// content of inlined runtime dockerfile
     pub runtime_base_dockerfile: Option<&'a str>,
     pub build_args: &'a BuildArgsRust,
+    /// add a final stage that starts the runtime with [`SHUTTLE_ENTRYPOINT_RS`]
+    pub self_hosted: bool,
 }
 
+/// Image label holding the version of shuttle-runtime that the image was built with.
+/// Rendered into the Dockerfile template from here, and read back by cargo-shuttle.
+pub const RUNTIME_VERSION_LABEL: &str = "dev.shuttle.runtime-version";
+
+/// Source of the entrypoint used in self-hosted images.
+/// Needs to be placed in the build context as `shuttle_entrypoint.rs`.
+pub const SHUTTLE_ENTRYPOINT_RS: &str = include_str!("../templates/shuttle_entrypoint.rs");
+
 pub fn render_rust_dockerfile(build_args: &BuildArgsRust) -> String {
+    render(build_args, false)
+}
+
+/// Renders a Dockerfile that can be built and run without the Shuttle platform
+pub fn render_self_hosted_rust_dockerfile(build_args: &BuildArgsRust) -> String {
+    render(build_args, true)
+}
+
+fn render(build_args: &BuildArgsRust, self_hosted: bool) -> String {
+    let (runtime_image, runtime_base_dockerfile) = match build_args.runtime_image {
+        RuntimeImage::Default => (
+            "runtime-base",
+            Some(include_str!("../templates/runtime-base.Dockerfile")),
+        ),
+        RuntimeImage::DebianSlim => ("debian:bookworm-slim", None),
+        RuntimeImage::Distroless => ("gcr.io/distroless/cc-debian12", None),
+        RuntimeImage::StaticMusl => ("scratch", None),
+    };
+
     RustDockerfile {
         chef_image: "cargo-chef",
         cargo_chef_dockerfile: Some(include_str!("../templates/cargo-chef.Dockerfile")),
-        runtime_image: "runtime-base",
-        runtime_base_dockerfile: Some(include_str!("../templates/runtime-base.Dockerfile")),
+        runtime_image,
+        runtime_base_dockerfile,
         build_args,
+        self_hosted,
     }
     .render()
     .unwrap()
@@ -44,6 +74,7 @@ mod tests {
                 features: Some("asdf".into()),
                 ..Default::default()
             },
+            self_hosted: false,
         };
 
         let s = t.render().unwrap();
@@ -54,6 +85,82 @@ mod tests {
         assert!(s.contains("FROM rt AS runtime"));
         assert!(s.contains("RUN cargo chef cook --release --package hello --features asdf\n"));
         assert!(s.contains("mv /app/target/release/hello"));
+        assert!(!s.contains("LABEL"));
+    }
+
+    #[test]
+    fn rust_runtime_version_label() {
+        let s = render_rust_dockerfile(&BuildArgsRust {
+            shuttle_runtime_version: Some("0.57.3".into()),
+            package_name: Some("hello".into()),
+            ..Default::default()
+        });
+
+        assert!(s.contains(&format!(
+            "WORKDIR /app\nLABEL {RUNTIME_VERSION_LABEL}=\"0.57.3\"\n"
+        )));
+    }
+
+    #[test]
+    fn rust_cache_mounts() {
+        let s = render_rust_dockerfile(&BuildArgsRust {
+            package_name: Some("hello".into()),
+            cache_mounts: true,
+            sccache: true,
+            ..Default::default()
+        });
+
+        assert!(s.contains("ENV RUSTC_WRAPPER=sccache\n"));
+        assert!(s.contains(
+            "    --mount=type=cache,target=/root/.cache/sccache,sharing=locked \\\n    cargo chef cook --release --package hello\n"
+        ));
+        assert!(s.contains(
+            "    --mount=type=cache,target=/app/target,sharing=locked \\\n    --mount=type=cache,target=/root/.cache/sccache,sharing=locked \\\n    cargo build --release --package hello\n"
+        ));
+        assert!(s.contains("cp /app/target/release/hello /executable"));
+        assert!(!s.contains("mv /app/target/release/hello"));
+
+        // sccache alone also gets the cache mounts
+        let sccache_only = render_rust_dockerfile(&BuildArgsRust {
+            package_name: Some("hello".into()),
+            sccache: true,
+            ..Default::default()
+        });
+        assert_eq!(sccache_only, s);
+    }
+
+    #[test]
+    fn rust_static_musl() {
+        let s = render_rust_dockerfile(&BuildArgsRust {
+            package_name: Some("hello".into()),
+            runtime_image: RuntimeImage::StaticMusl,
+            ..Default::default()
+        });
+
+        assert!(!s.contains("FROM debian:bookworm-slim AS runtime-base"));
+        assert!(s.contains("FROM scratch AS runtime"));
+        assert!(s.contains("rustup target add x86_64-unknown-linux-musl\n"));
+        assert!(s.contains(
+            "RUN cargo build --release --package hello --target x86_64-unknown-linux-musl\n"
+        ));
+        assert!(s.contains("mv /app/target/x86_64-unknown-linux-musl/release/hello /executable"));
+        assert!(!s.contains("shuttle_setup_container.sh /tmp"));
+        assert!(s.contains("COPY --from=builder /etc/ssl/certs/ca-certificates.crt"));
+    }
+
+    #[test]
+    fn rust_self_hosted() {
+        let s = render_self_hosted_rust_dockerfile(&BuildArgsRust {
+            package_name: Some("hello".into()),
+            runtime_image: RuntimeImage::StaticMusl,
+            ..Default::default()
+        });
+
+        assert!(s.contains("FROM runtime AS self-hosted\n"));
+        assert!(s.contains(
+            "RUN rustc --edition 2021 -C opt-level=3 --target x86_64-unknown-linux-musl -o /shuttle_entrypoint shuttle_entrypoint.rs\n"
+        ));
+        assert!(s.ends_with("ENTRYPOINT [\"/usr/local/bin/shuttle_entrypoint\"]\n"));
     }
 
     #[test]
@@ -0,0 +1,202 @@
+//! Entrypoint for self-hosted images made with `shuttle build --eject`.
+//!
+//! Fills in defaults for the `SHUTTLE_*` env vars that the runtime expects,
+//! starts a provisioner stand-in on localhost, and then execs the runtime.
+//!
+//! The stand-in answers the runtime's provisioning requests from env vars:
+//! - `SHUTTLE_SECRET_<KEY>` becomes the secret `<KEY>`.
+//! - `SHUTTLE_RESOURCE_<TYPE>` is the output of a resource, where `<TYPE>` is the resource type
+//!   in upper case with `::` replaced by `_`, e.g. `SHUTTLE_RESOURCE_DATABASE_SHARED_POSTGRES`.
+//!   Database resources accept a plain connection string. Values starting with `{`, `[` or `"`
+//!   are passed on as raw JSON.
+//!
+//! Only depends on the standard library so that it can be built with a bare `rustc`.
+
+use std::env;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::process::CommandExt;
+use std::process::{exit, Command};
+use std::thread::sleep;
+use std::time::Duration;
+
+const RUNTIME: &str = "/usr/local/bin/runtime";
+const DEFAULT_PROVISIONER_PORT: &str = "8001";
+
+fn main() {
+    let args = env::args().collect::<Vec<_>>();
+    let port = env::var("SHUTTLE_PROVISIONER_PORT")
+        .unwrap_or_else(|_| DEFAULT_PROVISIONER_PORT.to_owned());
+
+    if args.get(1).map(String::as_str) == Some("--provisioner") {
+        serve(&port);
+        return;
+    }
+
+    let defaults = [
+        ("SHUTTLE_PROJECT_ID", "proj_SELFHOSTED"),
+        ("SHUTTLE_PROJECT_NAME", "shuttle-app"),
+        ("SHUTTLE_ENV", "deployment"),
+        ("SHUTTLE_RUNTIME_IP", "0.0.0.0"),
+        ("SHUTTLE_RUNTIME_PORT", "8000"),
+    ];
+    let mut runtime = Command::new(RUNTIME);
+    for (key, value) in defaults {
+        if env::var_os(key).is_none() {
+            runtime.env(key, value);
+        }
+    }
+    runtime.env("SHUTTLE_API", format!("http://127.0.0.1:{port}"));
+
+    let spawned = env::current_exe().and_then(|exe| Command::new(exe).arg("--provisioner").spawn());
+    if let Err(e) = spawned {
+        eprintln!("ERROR: Failed to start the provisioner stand-in: {e}");
+        exit(1);
+    }
+    // wait for the stand-in to accept connections before handing over to the runtime
+    for _ in 0..50 {
+        if TcpStream::connect(format!("127.0.0.1:{port}")).is_ok() {
+            break;
+        }
+        sleep(Duration::from_millis(100));
+    }
+
+    // exec so that the runtime receives the signals sent to the container
+    let e = runtime.exec();
+    eprintln!("ERROR: Failed to start {RUNTIME}: {e}");
+    exit(1);
+}
+
+fn serve(port: &str) {
+    let listener = match TcpListener::bind(format!("127.0.0.1:{port}")) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("ERROR: Provisioner stand-in failed to bind to port {port}: {e}");
+            exit(1);
+        }
+    };
+    for stream in listener.incoming().flatten() {
+        if let Err(e) = handle(stream) {
+            eprintln!("ERROR: Provisioner stand-in request failed: {e}");
+        }
+    }
+}
+
+fn handle(stream: TcpStream) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    let response = if method == "GET" && path.ends_with("/resources/secrets") {
+        Ok(resource_response("secrets", &secrets_json()))
+    } else if method == "POST" && path.ends_with("/resources") {
+        let body = String::from_utf8_lossy(&body);
+        match json_string_field(&body, "type") {
+            Some(r#type) if r#type == "secrets" => {
+                Ok(resource_response("secrets", &secrets_json()))
+            }
+            Some(r#type) => {
+                let var = format!(
+                    "SHUTTLE_RESOURCE_{}",
+                    r#type.to_ascii_uppercase().replace("::", "_")
+                );
+                match env::var(&var) {
+                    Ok(value) => Ok(resource_response(&r#type, &output_json(&value))),
+                    Err(_) => Err(format!(
+                        "Resource '{}' requested, but {var} is not set",
+                        r#type
+                    )),
+                }
+            }
+            None => Err("Could not find the resource type in the request".to_owned()),
+        }
+    } else {
+        Err(format!("Unsupported request: {method} {path}"))
+    };
+
+    let (status, body) = match response {
+        Ok(body) => ("200 OK", body),
+        Err(message) => {
+            eprintln!("ERROR: {message}");
+            (
+                "500 Internal Server Error",
+                format!(r#"{{"message":{}}}"#, json_string(&message)),
+            )
+        }
+    };
+    let mut stream = reader.into_inner();
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn resource_response(r#type: &str, output: &str) -> String {
+    format!(
+        r#"{{"type":{},"state":"ready","config":null,"output":{output}}}"#,
+        json_string(r#type)
+    )
+}
+
+fn secrets_json() -> String {
+    let mut secrets = env::vars()
+        .filter_map(|(k, v)| {
+            k.strip_prefix("SHUTTLE_SECRET_")
+                .map(|k| format!("{}:{}", json_string(k), json_string(&v)))
+        })
+        .collect::<Vec<_>>();
+    secrets.sort();
+    format!("{{{}}}", secrets.join(","))
+}
+
+fn output_json(value: &str) -> String {
+    if value.starts_with(['{', '[', '"']) {
+        value.to_owned()
+    } else {
+        json_string(value)
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Finds the string value of a top level field in a JSON object without a JSON parser
+fn json_string_field(json: &str, field: &str) -> Option<String> {
+    let key = format!("\"{field}\"");
+    let rest = json[json.find(&key)? + key.len()..].trim_start();
+    let rest = rest.strip_prefix(':')?.trim_start().strip_prefix('"')?;
+    Some(rest[..rest.find('"')?].to_owned())
+}
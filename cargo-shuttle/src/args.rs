@@ -3,9 +3,11 @@ use std::{
     fs::create_dir_all,
     io::{self, ErrorKind},
     path::{Path, PathBuf},
+    str::FromStr,
 };
 
 use anyhow::Context;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeDelta, Utc};
 use clap::{
     builder::{OsStringValueParser, PossibleValue, TypedValueParser},
     Args, Parser, Subcommand, ValueEnum,
@@ -13,10 +15,16 @@ use clap::{
 use clap_complete::Shell;
 use shuttle_common::{
     constants::EXAMPLES_REPO,
-    models::{deployment::BuildMeta, resource::ResourceType},
+    models::{
+        deployment::{BuildMeta, DeploymentResponse, DeploymentState},
+        project::ComputeTier,
+        resource::ResourceType,
+        telemetry::TelemetrySinkConfigDiscriminants,
+    },
+    tables::{TableOptions, TableSort},
 };
 
-use crate::util::cargo_metadata;
+use crate::util::{cargo_metadata, output::Template};
 
 #[derive(Parser)]
 #[command(
@@ -46,6 +54,10 @@ pub struct ShuttleArgs {
     /// Turn on tracing output for Shuttle libraries. (WARNING: can print sensitive data)
     #[arg(global = true, long, env = "SHUTTLE_DEBUG")]
     pub debug: bool,
+    /// Never prompt, and fail instead when input is needed.
+    /// Enabled automatically when stdin is not a terminal or when running in CI (`CI=true`)
+    #[arg(global = true, long, env = "SHUTTLE_NON_INTERACTIVE")]
+    pub non_interactive: bool,
     /// What format to print output in
     #[arg(
         global = true,
@@ -54,6 +66,9 @@ pub struct ShuttleArgs {
         default_value = "normal"
     )]
     pub output_mode: OutputMode,
+    /// Print fields of the JSON output using a template, e.g. '{{.id}}\t{{.state}}'. List responses print one line per item
+    #[arg(global = true, long, value_name = "TEMPLATE")]
+    pub format: Option<Template>,
     #[command(flatten)]
     pub project_args: ProjectArgs,
 
@@ -66,7 +81,9 @@ pub enum OutputMode {
     #[default]
     Normal,
     Json,
-    // TODO?: add table / non-table / raw table / raw logs variants?
+    /// One JSON value per line: one per list item, or one event per line when following a deployment
+    Ndjson,
+    Yaml,
 }
 
 /// Global project-related options
@@ -152,9 +169,21 @@ pub enum Command {
     /// Manage SSL certificates for custom domains
     #[command(subcommand, visible_alias = "cert")]
     Certificate(CertificateCommand),
+    /// Inspect the infrastructure declared in the code
+    #[command(subcommand)]
+    Infra(InfraCommand),
+    /// Manage exporting the project's telemetry to external sinks
+    #[command(subcommand)]
+    Telemetry(TelemetryCommand),
+    /// Manage teams and their members
+    #[command(subcommand)]
+    Team(TeamCommand),
     /// Show info about your Shuttle account
     #[command(visible_alias = "acc")]
-    Account,
+    Account {
+        #[command(subcommand)]
+        cmd: Option<AccountCommand>,
+    },
     /// Log in to the Shuttle platform
     Login(LoginArgs),
     /// Log out of the Shuttle platform
@@ -175,6 +204,149 @@ pub enum Command {
     Mcp(McpCommand),
 }
 
+#[derive(Subcommand)]
+pub enum AccountCommand {
+    /// Show the usage of all your projects in the current billing cycle
+    Usage(UsageArgs),
+}
+
+#[derive(Args)]
+pub struct UsageArgs {
+    /// Print the daily usage as CSV
+    #[arg(long)]
+    pub csv: bool,
+    /// Warn when usage reaches this percentage of a limit
+    #[arg(long, default_value_t = 80, value_name = "PERCENT")]
+    pub warn_threshold: u32,
+    #[command(flatten)]
+    pub table: TableArgs,
+}
+
+#[derive(Subcommand)]
+pub enum InfraCommand {
+    /// Show the instance size, scaling settings and resources that the next deploy would request
+    Show {
+        #[command(flatten)]
+        table: TableArgs,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TelemetryCommand {
+    /// Show which sinks telemetry is exported to
+    Status,
+    /// Export telemetry to a sink, replacing its previous config
+    ///
+    /// Tokens are read from their flag, environment variable, secrets file or a prompt.
+    #[command(subcommand)]
+    Enable(TelemetrySinkArgs),
+    /// Stop exporting telemetry to a sink
+    Disable { sink: TelemetrySink },
+}
+
+#[derive(Subcommand)]
+pub enum TelemetrySinkArgs {
+    /// Export to Betterstack
+    Betterstack {
+        /// Source token of the Betterstack source
+        #[arg(long, env = "BETTERSTACK_SOURCE_TOKEN", hide_env_values = true)]
+        source_token: Option<String>,
+        /// Host to send telemetry to
+        #[arg(long, default_value = "in-otel.logs.betterstack.com")]
+        ingesting_host: String,
+        #[command(flatten)]
+        secret_args: SecretsArgs,
+    },
+    /// Export to Datadog
+    Datadog {
+        /// Datadog API key
+        #[arg(long, env = "DATADOG_API_KEY", hide_env_values = true)]
+        api_key: Option<String>,
+        #[command(flatten)]
+        secret_args: SecretsArgs,
+    },
+    /// Export to Grafana Cloud
+    GrafanaCloud {
+        /// Grafana Cloud access policy token
+        #[arg(long, env = "GRAFANA_CLOUD_TOKEN", hide_env_values = true)]
+        token: Option<String>,
+        /// OTLP endpoint of the Grafana Cloud stack
+        #[arg(long)]
+        endpoint: Option<String>,
+        /// Instance ID of the Grafana Cloud stack
+        #[arg(long)]
+        instance_id: Option<String>,
+        #[command(flatten)]
+        secret_args: SecretsArgs,
+    },
+    /// Export to Logfire
+    Logfire {
+        /// Logfire write token
+        #[arg(long, env = "LOGFIRE_WRITE_TOKEN", hide_env_values = true)]
+        write_token: Option<String>,
+        /// Host to send telemetry to
+        #[arg(long, default_value = "logfire-api.pydantic.dev")]
+        endpoint: String,
+        #[command(flatten)]
+        secret_args: SecretsArgs,
+    },
+    /// Export to any OpenTelemetry collector
+    Generic {
+        /// Endpoint of the collector
+        #[arg(long)]
+        endpoint: Option<String>,
+        /// Bearer token to authenticate with
+        #[arg(long, env = "OTEL_BEARER_TOKEN", hide_env_values = true)]
+        bearer_token: Option<String>,
+        /// Full authorization header to authenticate with, instead of a bearer token
+        #[arg(
+            long,
+            env = "OTEL_AUTHORIZATION",
+            hide_env_values = true,
+            conflicts_with = "bearer_token"
+        )]
+        authorization: Option<String>,
+        /// Compression of the exported data, such as gzip
+        #[arg(long)]
+        compression: Option<String>,
+        /// Export with gRPC instead of HTTP
+        #[arg(long)]
+        grpc: bool,
+        /// Don't export logs
+        #[arg(long)]
+        no_logs: bool,
+        /// Don't export traces
+        #[arg(long)]
+        no_traces: bool,
+        /// Don't export metrics
+        #[arg(long)]
+        no_metrics: bool,
+        #[command(flatten)]
+        secret_args: SecretsArgs,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+pub enum TelemetrySink {
+    Betterstack,
+    Datadog,
+    GrafanaCloud,
+    Logfire,
+    Generic,
+}
+
+impl From<TelemetrySink> for TelemetrySinkConfigDiscriminants {
+    fn from(sink: TelemetrySink) -> Self {
+        match sink {
+            TelemetrySink::Betterstack => Self::Betterstack,
+            TelemetrySink::Datadog => Self::Datadog,
+            TelemetrySink::GrafanaCloud => Self::GrafanaCloud,
+            TelemetrySink::Logfire => Self::Logfire,
+            TelemetrySink::Generic => Self::GenericOtel,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 pub enum McpCommand {
     /// Start the Shuttle MCP server
@@ -201,6 +373,76 @@ pub struct TableArgs {
     /// Output tables without borders
     #[arg(long, default_value_t = false)]
     pub raw: bool,
+    /// Comma-separated keys of the columns to show, e.g. 'id,state,created_at'
+    #[arg(long, value_delimiter = ',', value_name = "COLUMNS")]
+    pub columns: Vec<String>,
+    /// Sort rows by a column key, optionally descending with ':desc', e.g. 'created_at:desc'
+    #[arg(long, value_name = "COLUMN[:desc]", value_parser = parse_table_sort)]
+    pub sort: Option<TableSort>,
+    /// Output tables without the header row
+    #[arg(long)]
+    pub no_header: bool,
+}
+
+impl From<TableArgs> for TableOptions {
+    fn from(args: TableArgs) -> Self {
+        Self {
+            raw: args.raw,
+            columns: args.columns,
+            sort: args.sort,
+            no_header: args.no_header,
+        }
+    }
+}
+
+#[derive(Args, Debug, Default)]
+pub struct WatchArgs {
+    /// Keep refreshing the status until the deployment is running, stopped or failed
+    #[arg(long, short = 'w')]
+    pub watch: bool,
+    /// Seconds between refreshes
+    #[arg(long, default_value = "2", requires = "watch")]
+    pub interval: u64,
+    /// Exit code when the deployment is running, or stopped after it was seen running
+    #[arg(long, default_value = "0", requires = "watch")]
+    pub exit_code: i32,
+    /// Exit code when the deployment failed, or is stopped without having been seen running
+    #[arg(long, default_value = "1", requires = "watch")]
+    pub failed_exit_code: i32,
+}
+
+/// Filters for listing deployments
+#[derive(Args, Default)]
+pub struct DeploymentFilterArgs {
+    /// Only show deployments in these states (comma-separated)
+    #[arg(long, value_delimiter = ',', value_parser = parse_deployment_state)]
+    pub state: Vec<DeploymentState>,
+    /// Only show deployments from this git branch
+    #[arg(long)]
+    pub branch: Option<String>,
+    /// Only show deployments created after this date (YYYY-MM-DD or RFC 3339) or duration ago (e.g. 12h, 7d, 2w)
+    #[arg(long, value_parser = parse_since)]
+    pub since: Option<DateTime<Utc>>,
+}
+
+impl DeploymentFilterArgs {
+    pub fn is_empty(&self) -> bool {
+        self.state.is_empty() && self.branch.is_none() && self.since.is_none()
+    }
+
+    pub fn matches(&self, deployment: &DeploymentResponse) -> bool {
+        (self.state.is_empty() || self.state.contains(&deployment.state))
+            && self.branch.as_ref().is_none_or(|b| {
+                deployment
+                    .build_meta
+                    .as_ref()
+                    .and_then(|m| m.git_branch.as_ref())
+                    == Some(b)
+            })
+            && self
+                .since
+                .is_none_or(|since| deployment.created_at >= since)
+    }
 }
 
 #[derive(Subcommand)]
@@ -209,13 +451,20 @@ pub enum DeploymentCommand {
     #[command(visible_alias = "ls")]
     List {
         /// Which page to display
-        #[arg(long, default_value = "1")]
+        #[arg(long, default_value = "1", conflicts_with = "all")]
         page: u32,
 
         /// How many deployments per page to display
         #[arg(long, default_value = "10", visible_alias = "per-page")]
         limit: u32,
 
+        /// List the deployments from all pages
+        #[arg(long)]
+        all: bool,
+
+        #[command(flatten)]
+        filter: DeploymentFilterArgs,
+
         #[command(flatten)]
         table: TableArgs,
     },
@@ -224,6 +473,9 @@ pub enum DeploymentCommand {
     Status {
         /// ID of deployment to get status for
         deployment_id: Option<String>,
+
+        #[command(flatten)]
+        watch: WatchArgs,
     },
     /// Redeploy a previous deployment (if possible)
     Redeploy {
@@ -233,6 +485,33 @@ pub enum DeploymentCommand {
         #[command(flatten)]
         tracking_args: DeploymentTrackingArgs,
     },
+    /// Compare two deployments
+    ///
+    /// Compares the state, git metadata and URIs. The instance size, replicas, build args,
+    /// secret keys and resource types are not recorded per deployment, so they are not compared.
+    Diff {
+        /// ID of the deployment to compare from
+        a: String,
+        /// ID of the deployment to compare to
+        b: String,
+
+        #[command(flatten)]
+        table: TableArgs,
+    },
+    /// Roll back to a previous deployment that reached the running state
+    Rollback {
+        /// ID of the deployment to roll back to
+        #[arg(long, conflicts_with = "steps")]
+        to: Option<String>,
+        /// How many builds to go back, counting only deployments known to have started the service
+        #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+        steps: u32,
+
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+        #[command(flatten)]
+        tracking_args: DeploymentTrackingArgs,
+    },
     /// Stop running deployment(s)
     Stop {
         #[command(flatten)]
@@ -295,6 +574,50 @@ pub enum CertificateCommand {
     },
 }
 
+#[derive(Subcommand)]
+pub enum TeamCommand {
+    /// List the teams you are a member of
+    #[command(visible_alias = "ls")]
+    List {
+        #[command(flatten)]
+        table: TableArgs,
+    },
+    /// List the members and pending invites of a team
+    Members {
+        /// ID or name of the team
+        team: String,
+        #[command(flatten)]
+        table: TableArgs,
+    },
+    /// Add a user to a team, or invite them by email
+    Invite {
+        /// ID or name of the team
+        team: String,
+        /// User ID of an existing Shuttle user, or an email address to send an invite to
+        member: String,
+        /// Role of the new member
+        #[arg(long, value_parser = ["admin", "member"])]
+        role: Option<String>,
+    },
+    /// Remove a member from a team
+    #[command(visible_alias = "rm")]
+    Remove {
+        /// ID or name of the team
+        team: String,
+        /// User ID of the member
+        user_id: String,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+    },
+    /// Leave a team
+    Leave {
+        /// ID or name of the team
+        team: String,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+    },
+}
+
 #[derive(Subcommand)]
 pub enum ProjectCommand {
     /// Create a project on Shuttle
@@ -305,7 +628,10 @@ pub enum ProjectCommand {
     Update(ProjectUpdateCommand),
     /// Get the status of this project on Shuttle
     #[command(visible_alias = "stat")]
-    Status,
+    Status {
+        #[command(flatten)]
+        watch: WatchArgs,
+    },
     /// List all projects you have access to
     #[command(visible_alias = "ls")]
     List {
@@ -317,6 +643,69 @@ pub enum ProjectCommand {
     Delete(ConfirmationArgs),
     /// Link this workspace to a Shuttle project
     Link,
+    /// Show the usage of this project in the current billing cycle
+    Usage(UsageArgs),
+    /// Transfer the project to a team or another user
+    Transfer {
+        #[command(flatten)]
+        target: ProjectTransferArgs,
+        #[command(flatten)]
+        confirmation: ConfirmationArgs,
+    },
+    /// Change the instance size or number of replicas of the project
+    Scale(ProjectScaleArgs),
+    /// Manage the GitHub repository linked to the project
+    #[command(subcommand, visible_alias = "gh")]
+    Github(ProjectGithubCommand),
+}
+
+#[derive(Subcommand)]
+pub enum ProjectGithubCommand {
+    /// Link a GitHub repository to the project
+    Link {
+        /// Repository as owner/name [default: the GitHub remote of the local git repository]
+        #[arg(long)]
+        repo: Option<String>,
+    },
+    /// Unlink the GitHub repository from the project
+    Unlink,
+    /// Set the branch of the linked repository that is deployed
+    Branch {
+        /// Name of the branch [default: select from the branches of the repository]
+        branch: Option<String>,
+    },
+    /// Deploy a branch of the linked GitHub repository
+    Deploy {
+        /// Name of the branch [default: the linked branch]
+        branch: Option<String>,
+        #[command(flatten)]
+        tracking_args: DeploymentTrackingArgs,
+    },
+}
+
+#[derive(Args, Debug)]
+#[group(required = true, multiple = true)]
+pub struct ProjectScaleArgs {
+    /// Instance size to run the project on (xs, s, m, l, xl, xxl)
+    #[arg(long, visible_alias = "instance-size", value_parser = parse_compute_tier)]
+    pub size: Option<ComputeTier>,
+    /// Number of instances to run
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=shuttle_ifc::MAX_REPLICAS as i64))]
+    pub replicas: Option<u8>,
+}
+
+#[derive(Args, Debug)]
+#[group(required = true, multiple = false)]
+pub struct ProjectTransferArgs {
+    /// ID or name of the team to transfer the project to
+    #[arg(long)]
+    pub team: Option<String>,
+    /// User ID of the user to transfer the project to
+    #[arg(long)]
+    pub user: Option<String>,
+    /// Move the project out of its current team
+    #[arg(long)]
+    pub remove_from_team: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -355,9 +744,30 @@ pub struct LogoutArgs {
 
 #[derive(Args, Default)]
 pub struct DeployArgs {
-    /// WIP: Deploy this Docker image instead of building one
-    #[arg(long, short = 'i', hide = true)]
+    /// Deploy this Docker image instead of building one
+    #[arg(long, short = 'i')]
     pub image: Option<String>,
+    /// Username for pulling the image from a private registry
+    #[arg(long, requires = "image")]
+    pub registry_username: Option<String>,
+    /// Password or token for pulling the image from a private registry
+    #[arg(
+        long,
+        env = "SHUTTLE_REGISTRY_PASSWORD",
+        hide_env_values = true,
+        requires = "registry_username"
+    )]
+    pub registry_password: Option<String>,
+    /// Send the credentials for the image's registry from the docker config (and its credential helpers)
+    #[arg(long, requires = "image", conflicts_with = "registry_username")]
+    pub registry_auth_from_docker_config: bool,
+    /// Don't check that the image was built with the local shuttle-runtime version
+    #[arg(long, requires = "image")]
+    pub no_runtime_check: bool,
+
+    /// Deploy the files committed at this git branch, tag or commit instead of the working directory
+    #[arg(long = "ref", value_name = "REF", conflicts_with = "image")]
+    pub git_ref: Option<String>,
 
     /// Allow deployment with uncommitted files
     #[arg(long, visible_alias = "ad")]
@@ -365,6 +775,15 @@ pub struct DeployArgs {
     /// Output the deployment archive to a file instead of sending a deployment request
     #[arg(long)]
     pub output_archive: Option<PathBuf>,
+    /// Don't run the smoke checks configured in Shuttle.toml
+    #[arg(long)]
+    pub no_smoke_checks: bool,
+    /// Reason for this deployment. Defaults to the git commit message.
+    #[arg(long, short = 'm')]
+    pub message: Option<String>,
+    /// Annotate the deployment, e.g. with a ticket ID or CI run URL. Can be repeated.
+    #[arg(long = "annotation", short = 'a', value_name = "KEY=VALUE", value_parser = parse_key_value)]
+    pub annotations: Vec<(String, String)>,
 
     #[command(flatten)]
     pub tracking_args: DeploymentTrackingArgs,
@@ -384,6 +803,9 @@ pub struct DeploymentTrackingArgs {
     /// Don't display timestamps and log origin tags
     #[arg(long)]
     pub raw: bool,
+    /// Stop following the deployment after this many seconds and exit with code 5
+    #[arg(long, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
 }
 
 #[derive(Args, Debug, Default)]
@@ -397,6 +819,10 @@ pub struct RunArgs {
     /// Don't display timestamps and log origin tags
     #[arg(long)]
     pub raw: bool,
+    /// Receive the OpenTelemetry data exported by the service, print its traces and list them on a local web page.
+    /// Requires the `setup-otel-exporter` feature of shuttle-runtime.
+    #[arg(long)]
+    pub otel: bool,
 
     #[command(flatten)]
     pub secret_args: SecretsArgs,
@@ -409,6 +835,12 @@ pub struct BuildArgs {
     /// Output the build archive to a file instead of building
     #[arg(long)]
     pub output_archive: Option<PathBuf>,
+    /// Write a self-contained Dockerfile and build context to this directory instead of building
+    #[arg(long, conflicts_with = "output_archive")]
+    pub eject: Option<PathBuf>,
+    /// Tag the image built with --docker as this reference and push it
+    #[arg(long, requires = "docker", value_name = "REGISTRY/REF")]
+    pub push: Option<String>,
     #[command(flatten)]
     pub inner: BuildArgsShared,
 }
@@ -582,6 +1014,86 @@ fn parse_path(path: OsString) -> Result<PathBuf, io::Error> {
     })
 }
 
+/// Helper function to parse a deployment state without falling back to an unknown state
+fn parse_deployment_state(s: &str) -> Result<DeploymentState, String> {
+    match DeploymentState::from_str(s) {
+        Ok(DeploymentState::Unknown(_)) | Err(_) => Err(format!(
+            "'{s}' is not a deployment state. Use one of: pending, building, running, in progress, stopped, stopping, failed"
+        )),
+        Ok(state) => Ok(state),
+    }
+}
+
+/// Helper function to parse an instance size without falling back to an unknown size
+fn parse_compute_tier(s: &str) -> Result<ComputeTier, String> {
+    match ComputeTier::from_str(&s.to_lowercase()) {
+        Ok(ComputeTier::Unknown(_)) | Err(_) => Err(format!(
+            "'{s}' is not an instance size. Use one of: xs, s, m, l, xl, xxl"
+        )),
+        Ok(tier) => Ok(tier),
+    }
+}
+
+/// Helper function to parse a date, a date and time, or a duration before now
+fn parse_since(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(t) = DateTime::parse_from_rfc3339(s) {
+        return Ok(t.to_utc());
+    }
+    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Ok(d.and_time(NaiveTime::MIN).and_utc());
+    }
+    let not_a_date =
+        || format!("'{s}' is not a date (YYYY-MM-DD or RFC 3339) or duration (e.g. 12h, 7d, 2w)");
+    // the unit is the last character, which might not be a single byte
+    let (i, unit) = s.char_indices().last().ok_or_else(not_a_date)?;
+    let n = s[..i].parse::<i64>().map_err(|_| not_a_date())?;
+    if n <= 0 {
+        return Err(format!("'{s}' should be a positive duration"));
+    }
+    let duration = match unit {
+        'm' => TimeDelta::try_minutes(n),
+        'h' => TimeDelta::try_hours(n),
+        'd' => TimeDelta::try_days(n),
+        'w' => TimeDelta::try_weeks(n),
+        _ => None,
+    }
+    .ok_or_else(|| {
+        format!("'{s}' is not a valid duration. Use a number followed by m, h, d or w")
+    })?;
+
+    Ok(Utc::now() - duration)
+}
+
+/// Helper function to parse a `key=value` pair
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((k, v)) if !k.trim().is_empty() => Ok((k.trim().to_owned(), v.to_owned())),
+        _ => Err(format!("'{s}' is not in the KEY=VALUE format")),
+    }
+}
+
+/// Helper function to parse a `column[:asc|:desc]` sort order
+fn parse_table_sort(s: &str) -> Result<TableSort, String> {
+    let (column, descending) = match s.rsplit_once(':') {
+        Some((column, "desc")) => (column, true),
+        Some((column, "asc")) => (column, false),
+        Some(_) => {
+            return Err(format!(
+                "'{s}' should be a column with an optional ':asc' or ':desc'"
+            ))
+        }
+        None => (s, false),
+    };
+    if column.trim().is_empty() {
+        return Err("a column to sort by is required".to_owned());
+    }
+
+    Ok(TableSort {
+        column: column.trim().to_owned(),
+        descending,
+    })
+}
+
 /// Helper function to parse, create if not exists, and return the absolute path
 pub(crate) fn create_and_parse_path(path: OsString) -> Result<PathBuf, io::Error> {
     // Create the directory if does not exist
@@ -606,6 +1118,119 @@ mod tests {
         ShuttleArgs::command().debug_assert();
     }
 
+    #[test]
+    fn since() {
+        assert_eq!(
+            parse_since("2025-03-01").unwrap().to_rfc3339(),
+            "2025-03-01T00:00:00+00:00"
+        );
+        assert_eq!(
+            parse_since("2025-03-01T12:00:00+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2025-03-01T10:00:00+00:00"
+        );
+        let week_ago = parse_since("1w").unwrap();
+        assert!(
+            (Utc::now() - week_ago - TimeDelta::weeks(1))
+                .num_seconds()
+                .abs()
+                < 5
+        );
+        assert!(parse_since("7y").is_err());
+        assert!(parse_since("yesterday").is_err());
+
+        // multi-byte last characters are an error, not a panic
+        assert!(parse_since("7é").is_err());
+        assert!(parse_since("é").is_err());
+        assert!(parse_since("").is_err());
+
+        assert!(parse_since("-7d").is_err());
+        assert!(parse_since("0h").is_err());
+    }
+
+    #[test]
+    fn deployment_state() {
+        assert_eq!(
+            parse_deployment_state("Failed").unwrap(),
+            DeploymentState::Failed
+        );
+        assert!(parse_deployment_state("broken").is_err());
+    }
+
+    #[test]
+    fn key_value() {
+        assert_eq!(
+            parse_key_value("ticket=OPS-1").unwrap(),
+            ("ticket".to_owned(), "OPS-1".to_owned())
+        );
+        assert_eq!(
+            parse_key_value("url=https://ci.example.com/?run=1").unwrap(),
+            ("url".to_owned(), "https://ci.example.com/?run=1".to_owned())
+        );
+        assert!(parse_key_value("novalue").is_err());
+        assert!(parse_key_value("=value").is_err());
+    }
+
+    #[test]
+    fn table_sort() {
+        let sort = |column: &str, descending| TableSort {
+            column: column.to_owned(),
+            descending,
+        };
+        assert_eq!(parse_table_sort("state").unwrap(), sort("state", false));
+        assert_eq!(
+            parse_table_sort("created_at:desc").unwrap(),
+            sort("created_at", true)
+        );
+        assert_eq!(parse_table_sort("name:asc").unwrap(), sort("name", false));
+        assert!(parse_table_sort("name:up").is_err());
+        assert!(parse_table_sort(":desc").is_err());
+    }
+
+    #[test]
+    fn compute_tier() {
+        assert_eq!(parse_compute_tier("m").unwrap(), ComputeTier::M);
+        assert_eq!(parse_compute_tier("XXL").unwrap(), ComputeTier::XXL);
+        assert!(parse_compute_tier("xxxl").is_err());
+    }
+
+    #[test]
+    fn registry_credentials() {
+        let parse = |args: &[&str]| {
+            ShuttleArgs::try_parse_from(
+                [&["cargo-shuttle", "deploy", "--image", "a/b"], args].concat(),
+            )
+        };
+        assert!(parse(&[]).is_ok());
+        assert!(parse(&["--registry-username", "u", "--registry-password", "p"]).is_ok());
+        assert!(parse(&["--registry-password", "p"]).is_err());
+        assert!(parse(&["--registry-auth-from-docker-config"]).is_ok());
+        assert!(parse(&[
+            "--registry-auth-from-docker-config",
+            "--registry-username",
+            "u"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn scale_replicas() {
+        let parse = |replicas: &str| {
+            ShuttleArgs::try_parse_from([
+                "cargo-shuttle",
+                "project",
+                "scale",
+                "--replicas",
+                replicas,
+            ])
+        };
+        assert!(parse("1").is_ok());
+        assert!(parse(&shuttle_ifc::MAX_REPLICAS.to_string()).is_ok());
+        assert!(parse("0").is_err());
+        assert!(parse(&(shuttle_ifc::MAX_REPLICAS + 1).to_string()).is_err());
+    }
+
     #[test]
     fn test_init_args_framework() {
         // pre-defined template (only hello world)
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use cargo_shuttle::{parse_args, setup_tracing, Binary, Shuttle};
+use cargo_shuttle::{exit_code, parse_args, setup_tracing, Binary, Shuttle};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -7,8 +7,15 @@ async fn main() -> Result<()> {
 
     setup_tracing(args.debug);
 
-    Shuttle::new(Binary::Shuttle, args.api_env.clone())?
+    let result = Shuttle::new(Binary::Shuttle, args.api_env.clone())?
         .run(args, provided_path_to_init)
-        .await
-        .map(|_| ())
+        .await;
+
+    if let Err(e) = result {
+        // same output as returning the error from main, but with a specific exit code
+        eprintln!("Error: {e:?}");
+        std::process::exit(exit_code(&e));
+    }
+
+    Ok(())
 }
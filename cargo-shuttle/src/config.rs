@@ -4,6 +4,7 @@ use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
 use shuttle_common::config::{Config, ConfigManager, GlobalConfig, GlobalConfigManager};
 use shuttle_common::constants::SHUTTLE_API_URL;
+use shuttle_common::models::deployment::{BuildArgsRust, RuntimeImage};
 use tracing::trace;
 
 use crate::args::ProjectArgs;
@@ -50,6 +51,32 @@ pub struct ProjectDeployConfig {
     pub include: Option<Vec<String>>,
     /// Set to true to deny deployments with uncommited changes. (use `--allow-dirty` to override)
     pub deny_dirty: Option<bool>,
+    /// Checks to run against the deployment once it is running
+    pub smoke: Option<ProjectSmokeConfig>,
+}
+/// Post-deploy smoke check config
+#[derive(Deserialize, Serialize, Default)]
+pub struct ProjectSmokeConfig {
+    #[serde(default)]
+    pub checks: Vec<SmokeCheck>,
+    /// Attempts per check before it is considered failed. Defaults to 5.
+    pub retries: Option<u32>,
+    /// Seconds between attempts. Defaults to 3.
+    pub interval: Option<u64>,
+    /// Seconds before a request times out. Defaults to 10.
+    pub timeout: Option<u64>,
+    /// Redeploy the previous running deployment if a check fails
+    pub rollback: Option<bool>,
+}
+/// A request to make against each URI of a deployment
+#[derive(Deserialize, Serialize, Default)]
+pub struct SmokeCheck {
+    /// Path to request with GET, e.g. `/healthz`
+    pub path: String,
+    /// Expected response status. Defaults to 200.
+    pub status: Option<u16>,
+    /// Regex that the response body should match
+    pub body: Option<String>,
 }
 /// Builder config
 #[derive(Deserialize, Serialize, Default)]
@@ -57,6 +84,27 @@ pub struct ProjectBuildConfig {
     /// Successor to `build_assets`.
     /// Patterns of files that should be copied from the build to the runtime container.
     pub assets: Option<Vec<String>>,
+    /// Use BuildKit cache mounts for cargo directories in the Dockerfile
+    pub cache_mounts: Option<bool>,
+    /// Compile with sccache in the Dockerfile, which implies `cache_mounts`
+    pub sccache: Option<bool>,
+    /// Base image for the runtime container
+    pub runtime_image: Option<RuntimeImage>,
+}
+
+impl ProjectBuildConfig {
+    /// Override the gathered build args with the options set in this config
+    pub fn apply_to(&self, build_args: &mut BuildArgsRust) {
+        if let Some(cache_mounts) = self.cache_mounts {
+            build_args.cache_mounts = cache_mounts;
+        }
+        if let Some(sccache) = self.sccache {
+            build_args.sccache = sccache;
+        }
+        if let Some(runtime_image) = self.runtime_image {
+            build_args.runtime_image = runtime_image;
+        }
+    }
 }
 
 /// .shuttle/config.toml schema (internal project-local config)
@@ -299,6 +347,31 @@ impl RequestContext {
             .and_then(|d| d.deny_dirty)
     }
 
+    /// # Panics
+    /// Panics if the project configuration has not been loaded.
+    pub fn smoke_config(&self) -> Option<&ProjectSmokeConfig> {
+        self.project
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .deploy
+            .as_ref()
+            .and_then(|d| d.smoke.as_ref())
+    }
+
+    /// # Panics
+    /// Panics if the project configuration has not been loaded.
+    pub fn build_config(&self) -> Option<&ProjectBuildConfig> {
+        self.project
+            .as_ref()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .build
+            .as_ref()
+    }
+
     /// Check if the current project id has been loaded.
     pub fn project_id_found(&self) -> bool {
         self.project_internal
@@ -0,0 +1,369 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    convert::Infallible,
+    fmt::Write,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use crossterm::style::Stylize;
+use http_body_util::{combinators::BoxBody, BodyExt, Full};
+use hyper::{
+    body::{self, Bytes},
+    server::conn::http1,
+    service::service_fn,
+    Method, Request as HyperRequest, Response,
+};
+use hyper_util::rt::TokioIo;
+use opentelemetry_proto::tonic::{
+    collector::trace::v1::ExportTraceServiceRequest,
+    trace::v1::{status::StatusCode, Span},
+};
+use prost::Message;
+use tokio::net::TcpListener;
+use tracing::{debug, trace};
+
+/// How many traces are kept for the trace viewer
+const MAX_TRACES: usize = 100;
+
+/// Receives the logs, traces and metrics that the runtime's `setup-otel-exporter` feature
+/// exports over OTLP/HTTP during local runs. Traces are printed and listed on a web page,
+/// logs and metrics are accepted and dropped since the logs are already read from stdout.
+pub struct OtelReceiver;
+
+impl OtelReceiver {
+    pub async fn run(
+        store: Arc<TraceStore>,
+        addr: &SocketAddr,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let listener = TcpListener::bind(addr).await?;
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let io = TokioIo::new(stream);
+
+            let store = Arc::clone(&store);
+            tokio::task::spawn(async move {
+                if let Err(err) = http1::Builder::new()
+                    .serve_connection(io, service_fn(|req| handler(Arc::clone(&store), req)))
+                    .await
+                {
+                    debug!("OpenTelemetry receiver connection error: {:?}", err);
+                }
+            });
+        }
+    }
+}
+
+/// The spans received so far, grouped by trace
+#[derive(Default)]
+pub struct TraceStore {
+    inner: Mutex<TraceStoreInner>,
+}
+
+#[derive(Default)]
+struct TraceStoreInner {
+    /// Trace IDs, oldest first
+    order: VecDeque<Vec<u8>>,
+    spans: HashMap<Vec<u8>, Vec<Span>>,
+}
+
+impl TraceStore {
+    /// Stores the spans and returns the traces whose root span was among them
+    fn insert(&self, spans: Vec<Span>) -> Vec<Vec<Span>> {
+        let mut inner = self.inner.lock().unwrap();
+        let mut finished = Vec::new();
+        for span in spans {
+            let trace_id = span.trace_id.clone();
+            if span.parent_span_id.is_empty() {
+                finished.push(trace_id.clone());
+            }
+            if !inner.spans.contains_key(&trace_id) {
+                inner.order.push_back(trace_id.clone());
+                if inner.order.len() > MAX_TRACES {
+                    let oldest = inner.order.pop_front().unwrap();
+                    inner.spans.remove(&oldest);
+                }
+            }
+            inner.spans.entry(trace_id).or_default().push(span);
+        }
+
+        finished
+            .iter()
+            .filter_map(|id| inner.spans.get(id).cloned())
+            .collect()
+    }
+
+    /// All stored traces, newest first
+    fn traces(&self) -> Vec<Vec<Span>> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .order
+            .iter()
+            .rev()
+            .filter_map(|id| inner.spans.get(id).cloned())
+            .collect()
+    }
+}
+
+async fn handler(
+    store: Arc<TraceStore>,
+    req: HyperRequest<body::Incoming>,
+) -> std::result::Result<Response<BoxBody<Bytes, Infallible>>, hyper::http::Error> {
+    let method = req.method().clone();
+    let path = req.uri().path().to_owned();
+    trace!("OpenTelemetry receiver got {method} {path}");
+
+    match (method, path.as_str()) {
+        (Method::POST, "/v1/traces") => {
+            let result = match req.into_body().collect().await {
+                Ok(body) => receive_traces(&store, body.to_bytes()),
+                Err(e) => Err(anyhow::Error::new(e).context("reading request body")),
+            };
+            match result {
+                Ok(()) => otlp_response(),
+                Err(e) => {
+                    debug!("Failed to receive exported traces: {e:#}");
+                    Response::builder()
+                        .status(400)
+                        .body(Full::new(Bytes::from(format!("{e:#}"))).boxed())
+                }
+            }
+        }
+        (Method::POST, "/v1/logs" | "/v1/metrics") => otlp_response(),
+        (Method::GET, "/") => Response::builder()
+            .header("content-type", "text/html; charset=utf-8")
+            .body(Full::new(Bytes::from(render_page(&store.traces()))).boxed()),
+        _ => Response::builder()
+            .status(404)
+            .body(Full::new(Bytes::new()).boxed()),
+    }
+}
+
+/// An empty export response, which tells the exporter that everything was accepted
+fn otlp_response() -> std::result::Result<Response<BoxBody<Bytes, Infallible>>, hyper::http::Error>
+{
+    Response::builder()
+        .header("content-type", "application/x-protobuf")
+        .body(Full::new(Bytes::new()).boxed())
+}
+
+fn receive_traces(store: &TraceStore, body: Bytes) -> Result<()> {
+    let request =
+        ExportTraceServiceRequest::decode(body).context("decoding OTLP protobuf request")?;
+    let spans = request
+        .resource_spans
+        .into_iter()
+        .flat_map(|r| r.scope_spans)
+        .flat_map(|s| s.spans)
+        .collect();
+
+    for spans in store.insert(spans) {
+        print!("{}", render_trace(&spans, true));
+    }
+
+    Ok(())
+}
+
+/// Orders the spans of a trace depth first, with siblings ordered by start time.
+/// Spans whose parent was not received are shown as roots.
+fn span_tree(spans: &[Span]) -> Vec<(usize, &Span)> {
+    let ids: HashSet<&[u8]> = spans.iter().map(|s| s.span_id.as_slice()).collect();
+    let mut children: HashMap<&[u8], Vec<&Span>> = HashMap::new();
+    let mut roots = Vec::new();
+    for span in spans {
+        if ids.contains(span.parent_span_id.as_slice()) {
+            children
+                .entry(span.parent_span_id.as_slice())
+                .or_default()
+                .push(span);
+        } else {
+            roots.push(span);
+        }
+    }
+
+    fn visit<'a>(
+        span: &'a Span,
+        depth: usize,
+        children: &HashMap<&[u8], Vec<&'a Span>>,
+        out: &mut Vec<(usize, &'a Span)>,
+    ) {
+        out.push((depth, span));
+        let mut kids = children
+            .get(span.span_id.as_slice())
+            .cloned()
+            .unwrap_or_default();
+        kids.sort_by_key(|s| s.start_time_unix_nano);
+        for kid in kids {
+            visit(kid, depth + 1, children, out);
+        }
+    }
+
+    roots.sort_by_key(|s| s.start_time_unix_nano);
+    let mut out = Vec::with_capacity(spans.len());
+    for root in roots {
+        visit(root, 0, &children, &mut out);
+    }
+
+    out
+}
+
+fn format_duration(nanos: u64) -> String {
+    match nanos {
+        n if n >= 1_000_000_000 => format!("{:.2}s", n as f64 / 1e9),
+        n if n >= 1_000_000 => format!("{:.2}ms", n as f64 / 1e6),
+        n if n >= 1_000 => format!("{:.2}µs", n as f64 / 1e3),
+        n => format!("{n}ns"),
+    }
+}
+
+fn span_duration(span: &Span) -> String {
+    format_duration(
+        span.end_time_unix_nano
+            .saturating_sub(span.start_time_unix_nano),
+    )
+}
+
+fn is_error(span: &Span) -> bool {
+    span.status
+        .as_ref()
+        .is_some_and(|s| s.code == StatusCode::Error as i32)
+}
+
+fn render_trace(spans: &[Span], colored: bool) -> String {
+    let mut s = String::new();
+    let trace_id = spans.first().map(|s| hex(&s.trace_id)).unwrap_or_default();
+    if colored {
+        writeln!(&mut s, "{}", format!("trace {trace_id}").bold()).unwrap();
+    } else {
+        writeln!(&mut s, "trace {trace_id}").unwrap();
+    }
+    for (depth, span) in span_tree(spans) {
+        let indent = "  ".repeat(depth + 1);
+        let duration = span_duration(span);
+        if !colored {
+            let error = if is_error(span) { " (error)" } else { "" };
+            writeln!(&mut s, "{indent}{} {duration}{error}", span.name).unwrap();
+        } else if is_error(span) {
+            writeln!(
+                &mut s,
+                "{indent}{} {}",
+                span.name.as_str().red(),
+                duration.red()
+            )
+            .unwrap();
+        } else {
+            writeln!(&mut s, "{indent}{} {}", span.name, duration.dark_grey()).unwrap();
+        }
+    }
+
+    s
+}
+
+fn render_page(traces: &[Vec<Span>]) -> String {
+    let mut s = String::from(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Shuttle local traces</title></head>\n\
+        <body style=\"font-family: monospace\">\n<h1>Recent traces</h1>\n",
+    );
+    if traces.is_empty() {
+        s.push_str("<p>No traces received yet</p>\n");
+    }
+    for spans in traces {
+        let root = span_tree(spans).into_iter().next().map(|(_, span)| span);
+        let summary = root
+            .map(|r| format!("{} {}", r.name, span_duration(r)))
+            .unwrap_or_default();
+        writeln!(
+            &mut s,
+            "<details><summary>{}</summary><pre>{}</pre></details>",
+            escape_html(&summary),
+            escape_html(&render_trace(spans, false)),
+        )
+        .unwrap();
+    }
+    s.push_str("</body></html>\n");
+
+    s
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans};
+
+    use super::*;
+
+    fn span(id: u8, parent: Option<u8>, name: &str, start: u64, end: u64) -> Span {
+        Span {
+            trace_id: vec![1; 16],
+            span_id: vec![id; 8],
+            parent_span_id: parent.map(|p| vec![p; 8]).unwrap_or_default(),
+            name: name.to_owned(),
+            start_time_unix_nano: start,
+            end_time_unix_nano: end,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn trace_tree() {
+        let spans = vec![
+            span(3, Some(1), "query", 2_000, 1_500_000),
+            span(2, Some(1), "auth", 1_000, 1_500),
+            span(4, Some(3), "connect", 3_000, 4_000),
+            span(1, None, "GET /users", 0, 2_500_000_000),
+        ];
+        assert_eq!(
+            render_trace(&spans, false),
+            "trace 01010101010101010101010101010101\n  \
+            GET /users 2.50s\n    \
+            auth 500ns\n    \
+            query 1.50ms\n      \
+            connect 1.00µs\n"
+        );
+    }
+
+    #[test]
+    fn trace_store() {
+        let store = TraceStore::default();
+        let mut decoded = span(9, None, "decoded", 0, 1);
+        decoded.trace_id = vec![9; 16];
+        let request = ExportTraceServiceRequest {
+            resource_spans: vec![ResourceSpans {
+                scope_spans: vec![ScopeSpans {
+                    spans: vec![decoded],
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }],
+        };
+        receive_traces(&store, request.encode_to_vec().into()).unwrap();
+        assert!(receive_traces(&store, Bytes::from_static(b"{}")).is_err());
+        assert_eq!(store.traces()[0][0].name, "decoded");
+
+        assert!(store
+            .insert(vec![span(2, Some(1), "child", 1, 2)])
+            .is_empty());
+        let finished = store.insert(vec![span(1, None, "root", 0, 3)]);
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].len(), 2);
+
+        for i in 0..MAX_TRACES as u8 {
+            let mut s = span(1, None, "root", 0, 1);
+            s.trace_id = vec![i + 2; 16];
+            store.insert(vec![s]);
+        }
+        assert_eq!(store.traces().len(), MAX_TRACES);
+        assert!(render_page(&store.traces()).contains("<summary>root 1ns</summary>"));
+    }
+}
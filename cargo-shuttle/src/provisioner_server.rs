@@ -32,7 +32,7 @@ use shuttle_common::{
         self, ProvisionResourceRequest, ResourceResponse, ResourceState, ResourceType,
     },
     secrets::Secret,
-    tables::get_resource_tables,
+    tables::{get_resource_tables, TableOptions},
     ContainerRequest, ContainerResponse, DatabaseInfo, DbInput,
 };
 use tokio::{net::TcpListener, time::sleep};
@@ -518,9 +518,9 @@ async fn provision(
             let table = get_resource_tables(
                 std::slice::from_ref(&response),
                 "local service",
-                false,
+                &TableOptions::default(),
                 true,
-            );
+            )?;
             println!("{table}");
             serde_json::to_vec(&response).unwrap()
         }
@@ -575,9 +575,9 @@ async fn provision(
             let table = get_resource_tables(
                 std::slice::from_ref(&response),
                 "local service",
-                false,
+                &TableOptions::default(),
                 true,
-            );
+            )?;
             println!("{table}");
 
             serde_json::to_vec(&response).unwrap()
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use anyhow::{bail, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Deserialize;
+use shuttle_builder::RUNTIME_VERSION_LABEL;
+use shuttle_common::models::deployment::RegistryCredentials;
+use tracing::debug;
+
+/// Key used for Docker Hub in the docker config
+const DOCKER_HUB_CONFIG_KEY: &str = "https://index.docker.io/v1/";
+
+/// The registry host of an image reference, following the same rules as docker
+pub fn registry_host(image: &str) -> &str {
+    match image.split_once('/') {
+        Some((first, _)) if first.contains(['.', ':']) || first == "localhost" => first,
+        _ => "docker.io",
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DockerConfig {
+    #[serde(default)]
+    auths: HashMap<String, DockerConfigAuth>,
+    creds_store: Option<String>,
+    #[serde(default)]
+    cred_helpers: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct DockerConfigAuth {
+    auth: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CredentialHelperOutput {
+    username: String,
+    secret: String,
+}
+
+fn docker_config_path() -> Option<PathBuf> {
+    std::env::var_os("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|h| h.join(".docker")))
+        .map(|d| d.join("config.json"))
+}
+
+/// Looks up credentials for the registry of `image` the same way `docker login` stores them:
+/// a credential helper for the registry, the default credential store, or the `auths` section.
+pub fn docker_config_credentials(image: &str) -> Result<Option<RegistryCredentials>> {
+    let Some(path) = docker_config_path().filter(|p| p.exists()) else {
+        return Ok(None);
+    };
+    debug!("Reading registry credentials from {}", path.display());
+    let config: DockerConfig =
+        serde_json::from_str(&std::fs::read_to_string(&path).context("reading docker config")?)
+            .context("parsing docker config")?;
+
+    let registry = registry_host(image);
+    let key = if registry == "docker.io" {
+        DOCKER_HUB_CONFIG_KEY
+    } else {
+        registry
+    };
+
+    if let Some(helper) = config.cred_helpers.get(key).or(config.creds_store.as_ref()) {
+        return credential_helper_get(helper, key).map(|o| {
+            o.map(|o| RegistryCredentials {
+                registry: registry.to_owned(),
+                username: o.username,
+                password: o.secret,
+            })
+        });
+    }
+
+    let Some(auth) = config
+        .auths
+        .get(key)
+        .or_else(|| config.auths.get(&format!("https://{key}")))
+        .and_then(|a| a.auth.as_ref())
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(decode_auth(registry, auth)?))
+}
+
+fn decode_auth(registry: &str, auth: &str) -> Result<RegistryCredentials> {
+    let decoded = String::from_utf8(
+        STANDARD
+            .decode(auth)
+            .context("decoding auth in docker config")?,
+    )
+    .context("auth in docker config should be valid utf8")?;
+    let (username, password) = decoded
+        .split_once(':')
+        .context("auth in docker config should be in the `username:password` format")?;
+
+    Ok(RegistryCredentials {
+        registry: registry.to_owned(),
+        username: username.to_owned(),
+        password: password.to_owned(),
+    })
+}
+
+fn credential_helper_get(helper: &str, key: &str) -> Result<Option<CredentialHelperOutput>> {
+    let program = format!("docker-credential-{helper}");
+    debug!("Getting registry credentials from {program}");
+    let mut child = std::process::Command::new(&program)
+        .arg("get")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("running {program}"))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin to be piped")
+        .write_all(key.as_bytes())?;
+    let output = child.wait_with_output()?;
+    // helpers exit with an error when they have no credentials for the registry
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("parsing output of {program}"))
+        .map(Some)
+}
+
+/// Reads the shuttle-runtime version label of an image, pulling it if it is not available locally
+pub async fn image_runtime_version(image: &str) -> Result<Option<String>> {
+    let inspect = || async {
+        tokio::process::Command::new("docker")
+            .arg("image")
+            .arg("inspect")
+            .arg("--format")
+            .arg(format!(
+                "{{{{ index .Config.Labels \"{RUNTIME_VERSION_LABEL}\" }}}}"
+            ))
+            .arg(image)
+            .stderr(Stdio::null())
+            .output()
+            .await
+            .context("spawning docker image inspect command")
+    };
+
+    let mut output = inspect().await?;
+    if !output.status.success() {
+        debug!("Image {image} not found locally, pulling it");
+        let pull = tokio::process::Command::new("docker")
+            .arg("pull")
+            .arg("--quiet")
+            .arg(image)
+            .stdout(Stdio::null())
+            .status()
+            .await
+            .context("spawning docker pull command")?;
+        if !pull.success() {
+            bail!("Failed to pull {image}");
+        }
+        output = inspect().await?;
+        if !output.status.success() {
+            bail!("Failed to inspect {image}");
+        }
+    }
+
+    let version = String::from_utf8(output.stdout)
+        .context("docker image inspect output should be valid utf8")?
+        .trim()
+        .to_owned();
+
+    // docker prints `<no value>` for missing labels
+    Ok((!version.is_empty() && version != "<no value>").then_some(version))
+}
+
+/// Tags `source` as `target` and pushes it
+pub async fn tag_and_push(source: &str, target: &str) -> Result<()> {
+    let tag = tokio::process::Command::new("docker")
+        .arg("tag")
+        .arg(source)
+        .arg(target)
+        .status()
+        .await
+        .context("spawning docker tag command")?;
+    if !tag.success() {
+        bail!("Docker tag error");
+    }
+
+    let push = tokio::process::Command::new("docker")
+        .arg("push")
+        .arg(target)
+        .kill_on_drop(true)
+        .status()
+        .await
+        .context("spawning docker push command")?;
+    if !push.success() {
+        bail!("Docker push error");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_hosts() {
+        assert_eq!(registry_host("ubuntu"), "docker.io");
+        assert_eq!(registry_host("library/ubuntu:24.04"), "docker.io");
+        assert_eq!(registry_host("ghcr.io/org/app:v1"), "ghcr.io");
+        assert_eq!(registry_host("localhost/app"), "localhost");
+        assert_eq!(
+            registry_host("127.0.0.1:5000/app@sha256:abc"),
+            "127.0.0.1:5000"
+        );
+    }
+
+    #[test]
+    fn decodes_auth() {
+        let c = decode_auth("ghcr.io", &STANDARD.encode("user:p:ss")).unwrap();
+        assert_eq!(c.registry, "ghcr.io");
+        assert_eq!(c.username, "user");
+        assert_eq!(c.password, "p:ss");
+
+        assert!(decode_auth("ghcr.io", &STANDARD.encode("nopassword")).is_err());
+    }
+}
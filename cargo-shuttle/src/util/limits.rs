@@ -0,0 +1,125 @@
+use anyhow::{bail, Context, Result};
+use shuttle_common::models::{
+    project::{ComputeTier, ProjectLimitsResponse},
+    user::AccountLimitsResponse,
+};
+
+/// Fails if the plan does not allow deploying the project, or allows a smaller instance size than requested
+pub fn check_deploy_limits(
+    limits: &ProjectLimitsResponse,
+    instance_size: Option<&ComputeTier>,
+) -> Result<()> {
+    if limits.can_deploy == Some(false) {
+        bail!("Your plan does not allow deploying this project. Check your plan and usage with `shuttle account usage`.");
+    }
+    if let Some(requested) = instance_size {
+        check_instance_size(limits, requested)
+            .context("checking the instance size in #[shuttle_runtime::main]")?;
+    }
+
+    Ok(())
+}
+
+/// Fails if the plan allows a smaller instance size than requested.
+/// Unknown tiers are left for the server to decide on.
+pub fn check_instance_size(limits: &ProjectLimitsResponse, requested: &ComputeTier) -> Result<()> {
+    let Some(max) = limits.max_compute_tier.as_ref() else {
+        return Ok(());
+    };
+    if !matches!(requested, ComputeTier::Unknown(_))
+        && !matches!(max, ComputeTier::Unknown(_))
+        && requested > max
+    {
+        bail!(
+            "The instance size {} exceeds what your plan allows. The largest available instance size is {}.",
+            requested.to_fancy_string(),
+            max.to_fancy_string(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Fails if the plan does not allow adding another custom domain.
+/// Limits that could not be fetched are left for the server to enforce.
+pub fn check_certificate_limits(
+    project_limits: Option<&ProjectLimitsResponse>,
+    account_limits: Option<&AccountLimitsResponse>,
+) -> Result<()> {
+    if let (Some(count), Some(limit)) = (
+        account_limits.and_then(|l| l.certificate_count),
+        account_limits.and_then(|l| l.certificate_limit),
+    ) {
+        if count >= limit {
+            bail!("Your plan allows {limit} custom domain(s), and {count} are in use. Delete one with `shuttle certificate delete` to add another.");
+        }
+    }
+    if project_limits.and_then(|l| l.can_add_certificate) == Some(false) {
+        bail!("Your plan does not allow adding custom domains to this project.");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(can_deploy: Option<bool>, max: Option<ComputeTier>) -> ProjectLimitsResponse {
+        ProjectLimitsResponse {
+            can_deploy,
+            can_add_certificate: None,
+            full_telemetry_enabled: None,
+            max_compute_tier: max,
+        }
+    }
+
+    #[test]
+    fn deploy_limits() {
+        assert!(check_deploy_limits(&limits(None, None), Some(&ComputeTier::XXL)).is_ok());
+        assert!(check_deploy_limits(&limits(Some(true), Some(ComputeTier::M)), None).is_ok());
+        assert!(check_deploy_limits(
+            &limits(Some(true), Some(ComputeTier::M)),
+            Some(&ComputeTier::M)
+        )
+        .is_ok());
+        assert!(check_deploy_limits(&limits(Some(false), None), None).is_err());
+
+        let err = check_deploy_limits(
+            &limits(Some(true), Some(ComputeTier::S)),
+            Some(&ComputeTier::L),
+        )
+        .unwrap_err();
+        let err = format!("{err:#}");
+        assert!(err.contains("Large (2 vCPU, 4 GB RAM)"), "{err}");
+        assert!(err.contains("Small (0.5 vCPU, 1 GB RAM)"), "{err}");
+
+        assert!(check_deploy_limits(
+            &limits(None, Some(ComputeTier::Unknown("XXXL".to_owned()))),
+            Some(&ComputeTier::XXL)
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn certificate_limits() {
+        let account = |count, limit| AccountLimitsResponse {
+            projects_count: None,
+            projects_limit: None,
+            active_projects_count: None,
+            active_projects_limit: None,
+            certificate_count: count,
+            certificate_limit: limit,
+        };
+        let mut project = limits(None, None);
+        assert!(check_certificate_limits(None, None).is_ok());
+        assert!(check_certificate_limits(Some(&project), Some(&account(None, None))).is_ok());
+        assert!(check_certificate_limits(Some(&project), Some(&account(Some(0), Some(1)))).is_ok());
+        assert!(
+            check_certificate_limits(Some(&project), Some(&account(Some(1), Some(1)))).is_err()
+        );
+        assert!(check_certificate_limits(None, Some(&account(Some(1), Some(1)))).is_err());
+        project.can_add_certificate = Some(false);
+        assert!(check_certificate_limits(Some(&project), None).is_err());
+    }
+}
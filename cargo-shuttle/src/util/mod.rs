@@ -1,4 +1,10 @@
 pub mod bacon;
+pub mod docker;
+pub mod limits;
+pub mod output;
+pub mod smoke;
+pub mod usage;
+pub mod watch;
 
 use std::{
     fmt::{Display, Write},
@@ -15,7 +21,9 @@ use clap_complete::{generate, Shell};
 use clap_mangen::Man;
 use crossterm::style::Stylize;
 use futures::StreamExt;
-use git2::{Repository, StatusOptions};
+use git2::{
+    Commit, FileMode, ObjectType, Oid, Repository, StatusOptions, TreeWalkMode, TreeWalkResult,
+};
 use indoc::writedoc;
 use shuttle_common::{
     constants::{SHUTTLE_GH_ISSUE_URL, SHUTTLE_GH_REPO_URL, SHUTTLE_INSTALL_DOCS_URL},
@@ -79,6 +87,125 @@ pub fn is_dirty(repo: &Repository) -> Result<()> {
     Ok(())
 }
 
+/// Writes the files of `commit` to `dest`, like `git archive` would.
+/// Submodules and symlinks are skipped.
+pub fn export_commit(repo: &Repository, commit: &Commit, dest: &Path) -> Result<()> {
+    let tree = commit.tree().context("getting tree of commit")?;
+    let mut result = Ok(());
+    tree.walk(TreeWalkMode::PreOrder, |root, entry| {
+        let Some(name) = entry.name() else {
+            warn!("Skipping file with a non utf-8 name in {root}");
+            return TreeWalkResult::Skip;
+        };
+        let path = dest.join(root).join(name);
+        let write = || -> Result<()> {
+            match entry.kind() {
+                Some(ObjectType::Tree) => std::fs::create_dir_all(&path)?,
+                Some(ObjectType::Blob) if entry.filemode() == i32::from(FileMode::Link) => {
+                    trace!("Skipping {root}{name}: is a symlink");
+                }
+                Some(ObjectType::Blob) => {
+                    let blob = repo.find_blob(entry.id())?;
+                    std::fs::write(&path, blob.content())?;
+                    #[cfg(unix)]
+                    if entry.filemode() == i32::from(FileMode::BlobExecutable) {
+                        use std::os::unix::fs::PermissionsExt;
+                        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))?;
+                    }
+                }
+                _ => warn!("Skipping {root}{name}: submodules are not exported"),
+            }
+            Ok(())
+        };
+        match write().with_context(|| format!("exporting {root}{name}")) {
+            Ok(()) => TreeWalkResult::Ok,
+            Err(e) => {
+                result = Err(e);
+                TreeWalkResult::Abort
+            }
+        }
+    })
+    .or_else(|e| {
+        // an aborted walk is reported as an error, the cause is in `result`
+        if result.is_err() {
+            Ok(())
+        } else {
+            Err(e)
+        }
+    })
+    .context("walking tree of commit")?;
+
+    result
+}
+
+/// The shorthand name of `git_ref` if it names a local or remote branch, such as `main` or `origin/main`
+pub fn git_ref_branch(repo: &Repository, git_ref: &str) -> Option<String> {
+    repo.resolve_reference_from_short_name(git_ref)
+        .ok()
+        .filter(|r| r.is_branch() || r.is_remote())
+        .and_then(|r| r.shorthand().map(ToOwned::to_owned))
+}
+
+/// One line summaries of the commits reachable from `to` but not from `from`, newest first
+pub fn git_log(repo_path: &Path, from: &str, to: &str) -> Result<Vec<String>> {
+    const MAX_COMMITS: usize = 50;
+    let repo = Repository::discover(repo_path)?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push(Oid::from_str(to)?)?;
+    revwalk.hide(Oid::from_str(from)?)?;
+
+    let mut lines = Vec::new();
+    for (i, id) in revwalk.enumerate() {
+        if i == MAX_COMMITS {
+            lines.push("...".to_owned());
+            break;
+        }
+        let commit = repo.find_commit(id?)?;
+        lines.push(format!(
+            "{:.7} {}",
+            commit.id().to_string(),
+            commit.summary().unwrap_or_default()
+        ));
+    }
+
+    Ok(lines)
+}
+
+/// Owner and name of the GitHub repository in a remote URL, such as `git@github.com:owner/repo.git`
+pub fn parse_github_remote(url: &str) -> Option<(String, String)> {
+    let (_, path) = url.split_once("github.com")?;
+    parse_github_repo(path.strip_prefix(['/', ':'])?)
+}
+
+/// Owner and name of a GitHub repository given as `owner/name`
+pub fn parse_github_repo(path: &str) -> Option<(String, String)> {
+    let path = path.trim_end_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    let (owner, name) = path.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+
+    Some((owner.to_owned(), name.to_owned()))
+}
+
+/// Owner and name of the GitHub repository of the git repository at `repo_path`.
+/// The `origin` remote is preferred over other remotes.
+pub fn github_repo_from_remote(repo_path: &Path) -> Result<(String, String)> {
+    let repo = Repository::discover(repo_path).context("finding git repository")?;
+    let remotes = repo.remotes().context("listing git remotes")?;
+    let mut names = remotes.iter().flatten().collect::<Vec<_>>();
+    names.sort_by_key(|name| *name != "origin");
+    for name in names {
+        let remote = repo.find_remote(name)?;
+        if let Some(owner_and_name) = remote.url().and_then(parse_github_remote) {
+            return Ok(owner_and_name);
+        }
+    }
+
+    bail!("No GitHub remote found in the git repository. Use `--repo owner/name` instead.")
+}
+
 pub async fn check_and_warn_runtime_version(path: &Path) -> Result<Option<String>> {
     if let Err(err) = check_version(path).await {
         warn!("{}", err);
@@ -308,3 +435,94 @@ pub fn cargo_metadata(path: &Path) -> anyhow::Result<cargo_metadata::Metadata> {
 
     Ok(meta)
 }
+
+#[cfg(test)]
+mod tests {
+    use git2::Signature;
+
+    use super::*;
+
+    #[test]
+    fn github_remotes() {
+        let repo = |owner: &str, name: &str| Some((owner.to_owned(), name.to_owned()));
+        assert_eq!(
+            parse_github_remote("git@github.com:shuttle-hq/shuttle.git"),
+            repo("shuttle-hq", "shuttle")
+        );
+        assert_eq!(
+            parse_github_remote("https://github.com/shuttle-hq/shuttle"),
+            repo("shuttle-hq", "shuttle")
+        );
+        assert_eq!(
+            parse_github_remote("ssh://git@github.com/shuttle-hq/shuttle.git/"),
+            repo("shuttle-hq", "shuttle")
+        );
+        assert_eq!(
+            parse_github_remote("https://token@github.com/a/b.git"),
+            repo("a", "b")
+        );
+        assert_eq!(parse_github_remote("git@gitlab.com:a/b.git"), None);
+        assert_eq!(parse_github_remote("https://github.com/a"), None);
+        assert_eq!(parse_github_remote("https://github.com/a/b/c"), None);
+
+        assert_eq!(parse_github_repo("a/b"), repo("a", "b"));
+        assert_eq!(parse_github_repo("a/b/c"), None);
+        assert_eq!(parse_github_repo("/b"), None);
+        assert_eq!(parse_github_repo("a"), None);
+    }
+
+    #[test]
+    fn export_commit_writes_committed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "v1").unwrap();
+        std::fs::write(dir.path().join("src").join("main.rs"), "fn main() {}").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let script = dir.path().join("shuttle_prebuild.sh");
+            std::fs::write(&script, "").unwrap();
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let sig = Signature::now("test", "test@example.com").unwrap();
+        let id = repo
+            .commit(Some("HEAD"), &sig, &sig, "first", &tree, &[])
+            .unwrap();
+
+        // uncommitted changes are not exported
+        std::fs::write(dir.path().join("Cargo.toml"), "v2").unwrap();
+        std::fs::write(dir.path().join("untracked"), "").unwrap();
+
+        let dest = tempfile::tempdir().unwrap();
+        export_commit(&repo, &repo.find_commit(id).unwrap(), dest.path()).unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(dest.path().join("Cargo.toml")).unwrap(),
+            "v1"
+        );
+        assert!(dest.path().join("src").join("main.rs").exists());
+        assert!(!dest.path().join("untracked").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |p: PathBuf| std::fs::metadata(p).unwrap().permissions().mode();
+            assert_eq!(mode(dest.path().join("shuttle_prebuild.sh")) & 0o111, 0o111);
+            assert_eq!(mode(dest.path().join("Cargo.toml")) & 0o111, 0);
+        }
+
+        let commit = repo.find_commit(id).unwrap();
+        repo.branch("feature", &commit, false).unwrap();
+        repo.tag_lightweight("v1", commit.as_object(), false)
+            .unwrap();
+        assert_eq!(git_ref_branch(&repo, "feature").as_deref(), Some("feature"));
+        assert_eq!(git_ref_branch(&repo, "v1"), None);
+        assert_eq!(git_ref_branch(&repo, &id.to_string()), None);
+    }
+}
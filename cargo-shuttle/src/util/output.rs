@@ -0,0 +1,167 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::args::OutputMode;
+
+/// A `--format` template, such as `{{.id}}\t{{.state}}`.
+///
+/// Placeholders select a field of the JSON response with a `.`-separated path (`{{.}}` is the whole value,
+/// `{{.deployments.0.id}}` indexes into arrays). Strings are printed as is, objects and arrays as compact JSON.
+/// Prefixing the path with `json` (`{{json .name}}`) prints the field as JSON even if it is a string.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Template(Vec<Segment>);
+
+#[derive(Clone, Debug, PartialEq)]
+enum Segment {
+    Text(String),
+    Field { path: Vec<String>, json: bool },
+}
+
+impl FromStr for Template {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // allow escaped tabs and newlines, as shells make the literal characters awkward to type
+        let s = s.replace("\\t", "\t").replace("\\n", "\n");
+        let mut segments = Vec::new();
+        let mut rest = s.as_str();
+        while let Some(start) = rest.find("{{") {
+            if start > 0 {
+                segments.push(Segment::Text(rest[..start].to_owned()));
+            }
+            let end = rest[start..].find("}}").with_context(|| {
+                format!("unclosed placeholder in template: '{}'", &rest[start..])
+            })?;
+            let inner = rest[start + 2..start + end].trim();
+            let (json, path) = match inner.strip_prefix("json ") {
+                Some(path) => (true, path.trim()),
+                None => (false, inner),
+            };
+            let Some(path) = path.strip_prefix('.') else {
+                bail!("placeholder should be a field path starting with '.': '{{{{{inner}}}}}'");
+            };
+            segments.push(Segment::Field {
+                path: path
+                    .split('.')
+                    .filter(|p| !p.is_empty())
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                json,
+            });
+            rest = &rest[start + end + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_owned()));
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+impl Template {
+    fn render(&self, value: &Value) -> String {
+        self.0
+            .iter()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.clone(),
+                Segment::Field { path, json } => {
+                    let field = path.iter().try_fold(value, |v, key| match v {
+                        Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+                        _ => v.get(key),
+                    });
+                    match field {
+                        None | Some(Value::Null) if !json => String::new(),
+                        Some(Value::String(s)) if !json => s.clone(),
+                        field => field.unwrap_or(&Value::Null).to_string(),
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+/// The items of a list response: the value itself if it is an array,
+/// or the array in an object with a single field, such as `{"deployments": [...]}`.
+fn list_items(value: &Value) -> Option<&Vec<Value>> {
+    match value {
+        Value::Array(items) => Some(items),
+        Value::Object(map) if map.len() == 1 => map.values().next().and_then(Value::as_array),
+        _ => None,
+    }
+}
+
+/// Renders a JSON API response in the given non-normal output mode.
+/// Templates and ndjson render list responses one item per line.
+pub fn render(mode: &OutputMode, template: Option<&Template>, raw_json: &str) -> Result<String> {
+    if template.is_none() && *mode == OutputMode::Json {
+        // print the response exactly as received
+        return Ok(raw_json.trim_end().to_owned());
+    }
+
+    let value: Value = serde_json::from_str(raw_json).context("parsing JSON response")?;
+    let lines = |f: &dyn Fn(&Value) -> String| match list_items(&value) {
+        Some(items) => items.iter().map(f).collect::<Vec<_>>().join("\n"),
+        None => f(&value),
+    };
+
+    Ok(match (template, mode) {
+        (Some(template), _) => lines(&|v| template.render(v)),
+        (None, OutputMode::Yaml) => serde_norway::to_string(&value)
+            .context("converting response to YAML")?
+            .trim_end()
+            .to_owned(),
+        (None, _) => lines(&|v| v.to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST: &str = r#"{"deployments":[{"id":"depl_1","state":"running","build_meta":{"git_branch":"main"}},{"id":"depl_2","state":"failed","build_meta":null}]}"#;
+
+    #[test]
+    fn templates() {
+        let t: Template = "{{.id}}\\t{{ .state }} {{.build_meta.git_branch}}"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            render(&OutputMode::Normal, Some(&t), LIST).unwrap(),
+            "depl_1\trunning main\ndepl_2\tfailed "
+        );
+
+        let t: Template = "{{json .deployments.1.id}} {{.deployments.0.build_meta}}"
+            .parse()
+            .unwrap();
+        // not a single-field list when indexing from the top
+        assert_eq!(
+            t.render(&serde_json::from_str(LIST).unwrap()),
+            r#""depl_2" {"git_branch":"main"}"#
+        );
+
+        let t: Template = "{{.}}".parse().unwrap();
+        assert_eq!(
+            render(&OutputMode::Json, Some(&t), r#""Resource deleted""#).unwrap(),
+            "Resource deleted"
+        );
+
+        assert!("{{.id".parse::<Template>().is_err());
+        assert!("{{id}}".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(render(&OutputMode::Json, None, LIST).unwrap(), LIST);
+        assert_eq!(
+            render(&OutputMode::Ndjson, None, LIST).unwrap(),
+            r#"{"build_meta":{"git_branch":"main"},"id":"depl_1","state":"running"}
+{"build_meta":null,"id":"depl_2","state":"failed"}"#
+        );
+        assert_eq!(
+            render(&OutputMode::Yaml, None, r#"{"id":"proj_1","name":"app"}"#).unwrap(),
+            "id: proj_1\nname: app"
+        );
+    }
+}
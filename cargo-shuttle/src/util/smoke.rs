@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use crossterm::style::Stylize;
+use regex::Regex;
+use reqwest::StatusCode;
+use tokio::time::sleep;
+use tracing::debug;
+
+use crate::config::{ProjectSmokeConfig, SmokeCheck};
+use crate::util::cargo_green_eprintln;
+
+const DEFAULT_RETRIES: u32 = 5;
+const DEFAULT_INTERVAL_SECS: u64 = 3;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Runs every check against every URI. Returns an error describing the checks that failed.
+pub async fn run_smoke_checks(uris: &[String], config: &ProjectSmokeConfig) -> Result<()> {
+    if uris.is_empty() {
+        bail!("Deployment has no URIs to run smoke checks against");
+    }
+    let retries = config.retries.unwrap_or(DEFAULT_RETRIES).max(1);
+    let interval = Duration::from_secs(config.interval.unwrap_or(DEFAULT_INTERVAL_SECS));
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(
+            config.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS),
+        ))
+        .build()?;
+
+    let mut failures = Vec::new();
+    for check in &config.checks {
+        let body_regex = check
+            .body
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .with_context(|| format!("parsing body regex of smoke check for {}", check.path))?;
+
+        for uri in uris {
+            let url = format!(
+                "{}/{}",
+                uri.trim_end_matches('/'),
+                check.path.trim_start_matches('/')
+            );
+            cargo_green_eprintln("Checking", &url);
+
+            let mut result = Ok(());
+            for attempt in 1..=retries {
+                result = match client.get(&url).send().await {
+                    Ok(r) => {
+                        let status = r.status();
+                        let body = r.text().await.unwrap_or_default();
+                        check_response(check, body_regex.as_ref(), status, &body)
+                    }
+                    Err(e) => Err(format!("request failed: {e}")),
+                };
+                match result {
+                    Ok(()) => break,
+                    Err(ref e) if attempt < retries => {
+                        debug!("Smoke check attempt {attempt} for {url} failed: {e}");
+                        sleep(interval).await;
+                    }
+                    Err(_) => (),
+                }
+            }
+
+            if let Err(e) = result {
+                eprintln!("{}", format!("Smoke check failed for {url}: {e}").red());
+                failures.push(url);
+            }
+        }
+    }
+
+    if !failures.is_empty() {
+        bail!(
+            "{} smoke check(s) failed: {}",
+            failures.len(),
+            failures.join(", ")
+        );
+    }
+    cargo_green_eprintln("Finished", "smoke checks");
+
+    Ok(())
+}
+
+fn check_response(
+    check: &SmokeCheck,
+    body_regex: Option<&Regex>,
+    status: StatusCode,
+    body: &str,
+) -> Result<(), String> {
+    let expected = check.status.unwrap_or(200);
+    if status.as_u16() != expected {
+        return Err(format!("expected status {expected}, got {status}"));
+    }
+    if let Some(re) = body_regex {
+        if !re.is_match(body) {
+            return Err(format!("body does not match `{re}`"));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn responses_are_checked() {
+        let check = SmokeCheck {
+            path: "/healthz".into(),
+            status: None,
+            body: Some("^ok".into()),
+        };
+        let re = Regex::new("^ok").unwrap();
+
+        assert!(check_response(&check, Some(&re), StatusCode::OK, "ok!").is_ok());
+        assert!(check_response(&check, Some(&re), StatusCode::OK, "not ok").is_err());
+        assert!(check_response(&check, None, StatusCode::SERVICE_UNAVAILABLE, "").is_err());
+
+        let check = SmokeCheck {
+            status: Some(404),
+            ..check
+        };
+        assert!(check_response(&check, None, StatusCode::NOT_FOUND, "").is_ok());
+    }
+
+    #[test]
+    fn config_from_toml() {
+        let config: ProjectSmokeConfig = toml::from_str(
+            r#"
+            rollback = true
+            retries = 2
+
+            [[checks]]
+            path = "/healthz"
+
+            [[checks]]
+            path = "/api/version"
+            status = 200
+            body = "\\d+\\.\\d+"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.rollback, Some(true));
+        assert_eq!(config.checks.len(), 2);
+        assert_eq!(config.checks[1].body.as_deref(), Some(r"\d+\.\d+"));
+    }
+}
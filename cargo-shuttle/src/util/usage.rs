@@ -0,0 +1,271 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use crossterm::style::Stylize;
+use shuttle_common::{
+    models::{
+        project::{ProjectUsageDaily, ProjectUsageResponse},
+        user::{UserBillingCycle, UserUsageResponse},
+    },
+    tables::{get_usage_daily_table, sparkline, TableOptions},
+};
+
+/// Percentage of a limit that has been used
+fn percent(used: u32, limit: u32) -> Option<u32> {
+    (limit > 0).then(|| (used as u64 * 100 / limit as u64) as u32)
+}
+
+fn used_of_limit(used: u32, limit: u32) -> String {
+    match percent(used, limit) {
+        Some(p) => format!("{used} / {limit} ({p}%)"),
+        None => format!("{used} / {limit}"),
+    }
+}
+
+/// Warnings for the limits of the account, or only the given project, that have reached `threshold` percent
+pub fn usage_warnings(
+    usage: &UserUsageResponse,
+    project: Option<&str>,
+    threshold: u32,
+) -> Vec<String> {
+    let mut limits = Vec::new();
+    if project.is_none() {
+        if let Some(ref user) = usage.user {
+            limits.push((
+                "Projects".to_owned(),
+                user.projects.used,
+                user.projects.limit,
+            ));
+            limits.push((
+                "Custom domains".to_owned(),
+                user.custom_domains.used,
+                user.custom_domains.limit,
+            ));
+            if let Some(ref team_members) = user.team_members {
+                limits.push((
+                    "Team members".to_owned(),
+                    team_members.used,
+                    team_members.limit,
+                ));
+            }
+        }
+    }
+    let mut projects = usage
+        .projects
+        .iter()
+        .filter(|(id, _)| project.is_none_or(|p| p == id.as_str()))
+        .collect::<Vec<_>>();
+    projects.sort_by_key(|(id, _)| id.as_str());
+    for (id, p) in projects {
+        limits.push((
+            format!("Build minutes of {id}"),
+            p.build_minutes.used,
+            p.build_minutes.limit,
+        ));
+    }
+
+    limits
+        .into_iter()
+        .filter_map(|(name, used, limit)| {
+            percent(used, limit)
+                .filter(|p| *p >= threshold)
+                .map(|p| format!("{name} at {p}% of the limit ({used} / {limit})"))
+        })
+        .collect()
+}
+
+pub fn billing_cycle_line(cycle: Option<&UserBillingCycle>) -> String {
+    match cycle {
+        Some(c) => format!("Usage for the billing cycle {} to {}", c.start, c.end),
+        None => "No usage recorded in the current billing cycle".to_owned(),
+    }
+}
+
+/// Totals for the cycle, sparklines of the daily utilisation, and a table of the daily usage
+pub fn project_usage_view(
+    project_id: &str,
+    usage: &ProjectUsageResponse,
+    table_options: &TableOptions,
+) -> Result<String> {
+    let mut daily = usage.daily.clone();
+    daily.sort_by_key(|d| d.isodate);
+
+    let mut s = String::new();
+    writeln!(s, "{}", format!("Project {project_id}").bold())?;
+    writeln!(
+        s,
+        "  Build minutes: {}",
+        used_of_limit(usage.build_minutes.used, usage.build_minutes.limit)
+    )?;
+    writeln!(
+        s,
+        "  vCPU hours: {:.2} reserved, {:.2} billable",
+        usage.vcpu.reserved_hours, usage.vcpu.billable_hours
+    )?;
+    if !daily.is_empty() {
+        writeln!(
+            s,
+            "  CPU:    {}",
+            sparkline(daily.iter().map(ProjectUsageDaily::cpu_utilisation))
+        )?;
+        writeln!(
+            s,
+            "  Memory: {}",
+            sparkline(daily.iter().map(ProjectUsageDaily::memory_utilisation))
+        )?;
+        writeln!(s, "{}", get_usage_daily_table(&daily, table_options)?)?;
+    }
+
+    Ok(s)
+}
+
+/// The account limits, followed by the usage of each project
+pub fn account_usage_view(
+    usage: &UserUsageResponse,
+    table_options: &TableOptions,
+) -> Result<String> {
+    let mut s = String::new();
+    writeln!(
+        s,
+        "{}",
+        billing_cycle_line(usage.billing_cycle.as_ref()).bold()
+    )?;
+    if let Some(ref user) = usage.user {
+        writeln!(
+            s,
+            "  Projects: {}",
+            used_of_limit(user.projects.used, user.projects.limit)
+        )?;
+        writeln!(
+            s,
+            "  Custom domains: {}",
+            used_of_limit(user.custom_domains.used, user.custom_domains.limit)
+        )?;
+        if let Some(ref team_members) = user.team_members {
+            writeln!(
+                s,
+                "  Team members: {}",
+                used_of_limit(team_members.used, team_members.limit)
+            )?;
+        }
+    }
+
+    let mut projects = usage.projects.iter().collect::<Vec<_>>();
+    projects.sort_by_key(|(id, _)| id.as_str());
+    for (id, project) in projects {
+        writeln!(s)?;
+        write!(s, "{}", project_usage_view(id, project, table_options)?)?;
+    }
+
+    Ok(s)
+}
+
+/// Daily usage as CSV, one row per project and day
+pub fn usage_csv<'a>(rows: impl IntoIterator<Item = (&'a str, &'a ProjectUsageDaily)>) -> String {
+    let mut rows = rows.into_iter().collect::<Vec<_>>();
+    rows.sort_by_key(|(id, d)| (*id, d.isodate));
+
+    let mut csv = String::from(
+        "project_id,date,build_minutes,runtime_minutes,reserved_vcpu_hours,billable_vcpu_hours,\
+        avg_cpu_utilised,min_cpu_reserved,max_cpu_reserved,avg_mem_utilised,min_mem_reserved,max_mem_reserved\n",
+    );
+    for (id, d) in rows {
+        // ids and numbers never need quoting
+        writeln!(
+            csv,
+            "{id},{},{},{},{},{},{},{},{},{},{},{}",
+            d.isodate,
+            d.build_minutes,
+            d.runtime_minutes,
+            d.reserved_vcpu_hours,
+            d.billable_vcpu_hours,
+            d.avg_cpu_utilised,
+            d.min_cpu_reserved,
+            d.max_cpu_reserved,
+            d.avg_mem_utilised,
+            d.min_mem_reserved,
+            d.max_mem_reserved,
+        )
+        .unwrap();
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use shuttle_common::models::{
+        project::ProjectUsageBuild,
+        user::{UserOverviewResponse, UserUsageCustomDomains, UserUsageProjects},
+    };
+
+    use super::*;
+
+    fn usage() -> UserUsageResponse {
+        UserUsageResponse {
+            billing_cycle: None,
+            user: Some(UserOverviewResponse {
+                custom_domains: UserUsageCustomDomains { used: 1, limit: 1 },
+                projects: UserUsageProjects { used: 2, limit: 5 },
+                team_members: None,
+            }),
+            projects: [
+                (
+                    "proj_a".to_owned(),
+                    ProjectUsageResponse {
+                        build_minutes: ProjectUsageBuild {
+                            used: 450,
+                            limit: 500,
+                        },
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "proj_b".to_owned(),
+                    ProjectUsageResponse {
+                        build_minutes: ProjectUsageBuild {
+                            used: 10,
+                            limit: 500,
+                        },
+                        ..Default::default()
+                    },
+                ),
+            ]
+            .into(),
+        }
+    }
+
+    #[test]
+    fn warnings() {
+        assert_eq!(
+            usage_warnings(&usage(), None, 80),
+            [
+                "Custom domains at 100% of the limit (1 / 1)",
+                "Build minutes of proj_a at 90% of the limit (450 / 500)",
+            ]
+        );
+        assert_eq!(
+            usage_warnings(&usage(), Some("proj_b"), 80),
+            Vec::<String>::new()
+        );
+        assert_eq!(usage_warnings(&usage(), Some("proj_b"), 0).len(), 1);
+    }
+
+    #[test]
+    fn csv() {
+        let day = |date: &str| ProjectUsageDaily {
+            isodate: date.parse().unwrap(),
+            build_minutes: 3,
+            avg_cpu_utilised: 0.25,
+            max_cpu_reserved: 0.5,
+            ..Default::default()
+        };
+        let (d1, d2) = (day("2025-03-02"), day("2025-03-01"));
+        let csv = usage_csv([("proj_a", &d1), ("proj_a", &d2)]);
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("project_id,date,build_minutes,"));
+        assert_eq!(lines[1], "proj_a,2025-03-01,3,0,0,0,0.25,0,0.5,0,0,0");
+        assert!(lines[2].starts_with("proj_a,2025-03-02,"));
+    }
+}
@@ -0,0 +1,143 @@
+use std::future::Future;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use crossterm::{
+    cursor::MoveUp,
+    queue,
+    style::Stylize,
+    terminal::{self, Clear, ClearType},
+};
+use shuttle_common::models::deployment::DeploymentState;
+
+use crate::args::WatchArgs;
+use crate::ExitWithCode;
+
+/// Repeatedly fetches a status and redraws it in place, together with the time spent in each state.
+/// Returns once the state is running, stopped or failed, which is also checked on the first fetch.
+/// Stopped only counts as a success if the deployment was seen running first.
+///
+/// `fetch` returns the state, the colored view, and the raw JSON that is passed to `print_json` on each change
+/// when it is given, instead of redrawing the view.
+pub async fn watch<Fut>(
+    args: &WatchArgs,
+    print_json: Option<impl Fn(&str) -> Result<()>>,
+    fetch: impl Fn() -> Fut,
+) -> Result<()>
+where
+    Fut: Future<Output = Result<(Option<DeploymentState>, String, String)>>,
+{
+    let mut history: Vec<(Option<DeploymentState>, Instant)> = Vec::new();
+    let mut drawn_lines = 0;
+
+    loop {
+        let (state, view, raw_json) = fetch().await?;
+        let changed = history.last().is_none_or(|(s, _)| *s != state);
+        if changed {
+            history.push((state.clone(), Instant::now()));
+        }
+
+        if let Some(print_json) = print_json.as_ref() {
+            if changed {
+                print_json(&raw_json)?;
+            }
+        } else {
+            let mut out = stdout();
+            if drawn_lines > 0 {
+                queue!(out, MoveUp(drawn_lines), Clear(ClearType::FromCursorDown))?;
+            }
+            let text = format!("{}\n{}", view.trim_end(), states_view(&history, changed));
+            write!(out, "{text}")?;
+            out.flush()?;
+            drawn_lines = terminal_rows(&text, terminal::size().map_or(0, |(cols, _)| cols));
+        }
+
+        let was_running = history
+            .iter()
+            .any(|(s, _)| *s == Some(DeploymentState::Running));
+        match state {
+            Some(DeploymentState::Running) => return success(args),
+            Some(DeploymentState::Stopped) if was_running => return success(args),
+            Some(DeploymentState::Stopped | DeploymentState::Failed) => {
+                return Err(ExitWithCode(args.failed_exit_code).into());
+            }
+            _ => (),
+        }
+
+        tokio::time::sleep(Duration::from_secs(args.interval.max(1))).await;
+    }
+}
+
+fn success(args: &WatchArgs) -> Result<()> {
+    match args.exit_code {
+        0 => Ok(()),
+        code => Err(ExitWithCode(code).into()),
+    }
+}
+
+/// How many terminal rows the text takes up, including the lines that wrap
+fn terminal_rows(text: &str, cols: u16) -> u16 {
+    text.lines()
+        .map(|line| match (console::measure_text_width(line), cols) {
+            (0, _) | (_, 0) => 1,
+            (width, cols) => width.div_ceil(cols as usize),
+        })
+        .sum::<usize>() as u16
+}
+
+/// One line per state seen, with the time spent in it. The current state is highlighted when it just changed.
+fn states_view(history: &[(Option<DeploymentState>, Instant)], changed: bool) -> String {
+    let mut s = String::from("\n");
+    for (i, (state, since)) in history.iter().enumerate() {
+        let until = history.get(i + 1).map_or_else(Instant::now, |(_, t)| *t);
+        let name = match state {
+            Some(state) => format!("{:<12}", state.to_string())
+                .with(state.get_color_crossterm())
+                .to_string(),
+            None => format!("{:<12}", "no deployment"),
+        };
+        let current = i == history.len() - 1;
+        let name = if current && changed {
+            name.bold().reverse().to_string()
+        } else {
+            name
+        };
+        s.push_str(&format!(
+            "  {name} {}{}\n",
+            format_duration(until - *since),
+            if current { " (current)" } else { "" }
+        ));
+    }
+
+    s
+}
+
+fn format_duration(d: Duration) -> String {
+    let secs = d.as_secs();
+    match secs {
+        0..60 => format!("{secs}s"),
+        60..3600 => format!("{}m {}s", secs / 60, secs % 60),
+        _ => format!("{}h {}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn durations() {
+        assert_eq!(format_duration(Duration::from_secs(5)), "5s");
+        assert_eq!(format_duration(Duration::from_secs(72)), "1m 12s");
+        assert_eq!(format_duration(Duration::from_secs(3725)), "1h 2m");
+    }
+
+    #[test]
+    fn wrapped_rows() {
+        let text = format!("{}\n\n{}", "a".repeat(25), "b".repeat(10).bold());
+        assert_eq!(terminal_rows(&text, 10), 5);
+        assert_eq!(terminal_rows(&text, 80), 3);
+        assert_eq!(terminal_rows(&text, 0), 3);
+    }
+}
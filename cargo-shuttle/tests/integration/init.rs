@@ -247,7 +247,9 @@ fn interactive_rocket_init() -> Result<(), Box<dyn std::error::Error>> {
     let mut command = Command::new(bin_path);
     command
         .args(["--offline", "init", "--force-name"])
-        .env("SHUTTLE_API_KEY", TEST_API_KEY);
+        .env("SHUTTLE_API_KEY", TEST_API_KEY)
+        // prompts are disabled in CI
+        .env_remove("CI");
     let mut session = rexpect::session::spawn_command(command, Some(EXPECT_TIMEOUT_MS))?;
 
     session.exp_string("Project name")?;
@@ -279,7 +281,9 @@ fn interactive_rocket_init_manually_choose_template() -> Result<(), Box<dyn std:
     let mut command = Command::new(bin_path);
     command
         .args(["--offline", "init", "--force-name"])
-        .env("SHUTTLE_API_KEY", TEST_API_KEY);
+        .env("SHUTTLE_API_KEY", TEST_API_KEY)
+        // prompts are disabled in CI
+        .env_remove("CI");
     let mut session = rexpect::session::spawn_command(command, Some(EXPECT_TIMEOUT_MS))?;
 
     session.exp_string("Project name")?;
@@ -311,7 +315,9 @@ fn interactive_rocket_init_dont_prompt_framework() -> Result<(), Box<dyn std::er
     let mut command = Command::new(bin_path);
     command
         .args(["--offline", "init", "--force-name", "--template", "rocket"])
-        .env("SHUTTLE_API_KEY", TEST_API_KEY);
+        .env("SHUTTLE_API_KEY", TEST_API_KEY)
+        // prompts are disabled in CI
+        .env_remove("CI");
     let mut session = rexpect::session::spawn_command(command, Some(EXPECT_TIMEOUT_MS))?;
 
     session.exp_string("Project name")?;
@@ -341,7 +347,9 @@ fn interactive_rocket_init_dont_prompt_name() -> Result<(), Box<dyn std::error::
     let mut command = Command::new(bin_path);
     command
         .args(["--offline", "init", "--force-name", "--name", "my-project"])
-        .env("SHUTTLE_API_KEY", TEST_API_KEY);
+        .env("SHUTTLE_API_KEY", TEST_API_KEY)
+        // prompts are disabled in CI
+        .env_remove("CI");
     let mut session = rexpect::session::spawn_command(command, Some(EXPECT_TIMEOUT_MS))?;
 
     session.exp_string("Where should we create this project?")?;
@@ -381,7 +389,9 @@ fn interactive_rocket_init_prompt_path_dirty_dir() -> Result<(), Box<dyn std::er
             "-t",
             "rocket",
         ])
-        .env("SHUTTLE_API_KEY", TEST_API_KEY);
+        .env("SHUTTLE_API_KEY", TEST_API_KEY)
+        // prompts are disabled in CI
+        .env_remove("CI");
     let mut session = rexpect::session::spawn_command(command, Some(EXPECT_TIMEOUT_MS))?;
 
     session.exp_string("Where should we create this project?")?;
@@ -1,5 +1,6 @@
 mod builder;
 mod init;
+mod non_interactive;
 mod run;
 
 #[tokio::test]
@@ -31,7 +32,10 @@ async fn fails_if_local_project_name_in_root() {
 async fn fails_if_no_project_id_found() {
     let bin_path = assert_cmd::cargo::cargo_bin!("shuttle");
     let mut command = std::process::Command::new(bin_path);
-    command.args(["--api-url", "http://shuttle.invalid", "--wd", "/", "logs"]);
+    command
+        .args(["--api-url", "http://shuttle.invalid", "--wd", "/", "logs"])
+        // prompts are disabled in CI
+        .env_remove("CI");
     let mut session = rexpect::session::spawn_command(command, Some(500)).unwrap();
 
     session.exp_string("error sending request for url").unwrap();
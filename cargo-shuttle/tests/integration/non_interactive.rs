@@ -0,0 +1,143 @@
+use std::process::{Command, Stdio};
+
+const TIMEOUT_MS: u64 = 500;
+
+/// Runs the CLI with stdin detached from any terminal, returning whether it succeeded and its stderr
+fn run_without_tty(args: &[&str]) -> (bool, String) {
+    let bin_path = assert_cmd::cargo::cargo_bin!("shuttle");
+    let output = Command::new(bin_path)
+        .args(["--api-url", "http://shuttle.invalid"])
+        .args(args)
+        .env("SHUTTLE_API_KEY", "0000000000000000")
+        .env_remove("CI")
+        .env_remove("SHUTTLE_NON_INTERACTIVE")
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+
+    (
+        output.status.success(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+#[test]
+fn flag_disables_prompts_in_terminal() {
+    let bin_path = assert_cmd::cargo::cargo_bin!("shuttle");
+    let mut command = Command::new(bin_path);
+    command
+        .args([
+            "--api-url",
+            "http://shuttle.invalid",
+            "--non-interactive",
+            "--id",
+            "proj_1",
+            "project",
+            "delete",
+        ])
+        .env_remove("CI");
+    let mut session = rexpect::session::spawn_command(command, Some(TIMEOUT_MS)).unwrap();
+
+    session
+        .exp_string("Can't prompt for confirmation in non-interactive mode. Use `--yes` instead.")
+        .unwrap();
+}
+
+#[test]
+fn ci_disables_prompts_in_terminal() {
+    let bin_path = assert_cmd::cargo::cargo_bin!("shuttle");
+    let mut command = Command::new(bin_path);
+    command
+        .args(["--api-url", "http://shuttle.invalid", "--wd", "/", "logs"])
+        .env("CI", "true");
+    let mut session = rexpect::session::spawn_command(command, Some(TIMEOUT_MS)).unwrap();
+
+    session
+        .exp_string("Can't prompt for a project to link in non-interactive mode. Use `--id` or `--name` instead.")
+        .unwrap();
+}
+
+#[test]
+fn confirmations_require_yes() {
+    for args in [
+        &["--id", "proj_1", "project", "delete"][..],
+        &["--id", "proj_1", "resource", "delete", "secrets"],
+        &["--id", "proj_1", "certificate", "delete", "example.com"],
+        &["--id", "proj_1", "deployment", "rollback"],
+        &["--id", "proj_1", "project", "transfer", "--user", "user_1"],
+        &["team", "remove", "team_1", "user_1"],
+        &["team", "leave", "team_1"],
+    ] {
+        let (success, stderr) = run_without_tty(args);
+        assert!(!success, "{args:?} should fail");
+        assert!(
+            stderr.contains("Use `--yes` instead."),
+            "{args:?} printed: {stderr}"
+        );
+    }
+
+    // answering the prompt with the flag proceeds to the API call
+    let (success, stderr) = run_without_tty(&["--id", "proj_1", "project", "delete", "--yes"]);
+    assert!(!success);
+    assert!(stderr.contains("error sending request for url"), "{stderr}");
+}
+
+#[test]
+fn project_linking_requires_id_or_name() {
+    let (success, stderr) = run_without_tty(&["--wd", "/", "deployment", "list"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("Use `--id` or `--name` instead."),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn github_branch_requires_branch() {
+    let (success, stderr) = run_without_tty(&["--id", "proj_1", "project", "github", "branch"]);
+    assert!(!success);
+    assert!(
+        stderr.contains(
+            "Can't prompt for a branch in non-interactive mode. Use a BRANCH argument instead."
+        ),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn telemetry_enable_requires_token() {
+    let (success, stderr) = run_without_tty(&[
+        "--wd",
+        "/",
+        "--id",
+        "proj_1",
+        "telemetry",
+        "enable",
+        "datadog",
+    ]);
+    assert!(!success);
+    assert!(
+        stderr.contains("Can't prompt for an API key in non-interactive mode."),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn login_requires_api_key() {
+    let (success, stderr) = run_without_tty(&["login"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("Use `--api-key` or `SHUTTLE_API_KEY` instead."),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn init_names_missing_args() {
+    let (success, stderr) = run_without_tty(&["init", "--offline", "--name", "my-project"]);
+    assert!(!success);
+    assert!(
+        stderr.contains("Use `--template` and a PATH argument instead."),
+        "{stderr}"
+    );
+}
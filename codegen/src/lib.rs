@@ -49,6 +49,25 @@ mod shuttle_main;
 /// ```
 ///
 /// More [shuttle managed resources can be found here](https://github.com/shuttle-hq/shuttle/tree/main/resources)
+///
+/// ## Customizing tracing
+/// The tracing subscriber installed by the runtime can be extended with your own layers, filter or sampler.
+/// Pass a function that takes and returns a `shuttle_runtime::TracingConfig`, and keep the `setup-tracing`
+/// or `setup-otel-exporter` feature of `shuttle-runtime` enabled:
+/// ```rust,ignore
+/// use shuttle_runtime::TracingConfig;
+///
+/// fn tracing(config: TracingConfig) -> TracingConfig {
+///     config
+///         .without_default_fmt()
+///         .with_layer(tracing_subscriber::fmt::layer().json())
+/// }
+///
+/// #[shuttle_runtime::main(tracing = tracing)]
+/// async fn main() -> shuttle_axum::ShuttleAxum {
+///     Ok(axum::Router::new().into())
+/// }
+/// ```
 #[proc_macro_error2::proc_macro_error]
 #[proc_macro_attribute]
 pub fn main(
@@ -1,11 +1,10 @@
 use proc_macro::TokenStream;
 use proc_macro_error2::emit_error;
-use quote::{quote, ToTokens};
-use shuttle_ifc::InfraAttrParser;
+use quote::{quote, quote_spanned, ToTokens};
+use shuttle_ifc::{BuilderPattern, InfraAttrParser};
 use syn::{
-    meta::parser, parse::Parse, parse_macro_input, parse_quote, punctuated::Punctuated,
-    spanned::Spanned, Attribute, Expr, ExprLit, FnArg, Ident, ItemFn, Lit, Pat, PatIdent, Path,
-    ReturnType, Signature, Stmt, Token, Type, TypePath,
+    meta::parser, parse_macro_input, parse_quote, spanned::Spanned, Attribute, Expr, ExprLit,
+    FnArg, Ident, ItemFn, Lit, Pat, PatIdent, ReturnType, Signature, Stmt, Type, TypePath,
 };
 
 /// Entrypoint for the `#[shuttle_runtime::main]` attribute macro.
@@ -36,6 +35,29 @@ pub(crate) fn tokens(attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut infra_parser = InfraAttrParser::default();
     let meta_parser = parser(|meta| infra_parser.parse_nested_meta(meta));
     parse_macro_input!(attr with meta_parser);
+    let start = match infra_parser.tracing() {
+        // a macro, so that a runtime without the `setup-tracing` feature can fail at the tracing function
+        Some(tracing) => quote_spanned! {tracing.span()=>
+            ::shuttle_runtime::__start_with_tracing!(
+                __loader,
+                __runner,
+                env!("CARGO_CRATE_NAME"),
+                env!("CARGO_PKG_VERSION"),
+                #tracing,
+            )
+        },
+        None => quote! {
+            ::shuttle_runtime::__internals::start(
+                __loader,
+                __runner,
+                env!("CARGO_CRATE_NAME"),
+                env!("CARGO_PKG_VERSION"),
+            )
+        },
+    };
+    if let Err(e) = infra_parser.into_infra() {
+        return e.into_compile_error().into();
+    }
 
     Into::into(quote! {
         fn main() {
@@ -45,13 +67,7 @@ pub(crate) fn tokens(attr: TokenStream, item: TokenStream) -> TokenStream {
                 .build()
                 .unwrap()
                 .block_on(async {
-                    ::shuttle_runtime::__internals::start(
-                        __loader,
-                        __runner,
-                        env!("CARGO_CRATE_NAME"),
-                        env!("CARGO_PKG_VERSION"),
-                    )
-                    .await;
+                    #start.await;
                 })
         }
 
@@ -78,48 +94,6 @@ struct Input {
     ty: Type,
 }
 
-/// Represents a builder pattern that a resource input gets turned into
-#[derive(Debug, PartialEq)]
-struct BuilderPattern {
-    /// Namespace path to the builder
-    path: Path,
-    /// Options to call the builder with
-    options: BuilderOptions,
-}
-
-/// The options (function names, values) to call the builder pattern with
-#[derive(Debug, Default, PartialEq)]
-struct BuilderOptions {
-    options: Punctuated<BuilderOption, Token![,]>,
-}
-
-/// One item in the builder pattern
-#[derive(Debug, PartialEq)]
-struct BuilderOption {
-    /// Identifier of the option to set
-    ident: Ident,
-    /// Value to set option to
-    value: Expr,
-}
-
-impl Parse for BuilderOptions {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        Ok(Self {
-            options: input.parse_terminated(BuilderOption::parse, Token![,])?,
-        })
-    }
-}
-
-impl Parse for BuilderOption {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let ident = input.parse()?;
-        let _equal: Token![=] = input.parse()?;
-        let value = input.parse()?;
-
-        Ok(Self { ident, value })
-    }
-}
-
 impl LoaderAndRunner {
     /// Modifies function identifier and inputs while extracting and constructing the Shuttle inputs
     pub(crate) fn from_item_fn(item_fn: &mut ItemFn) -> Option<Self> {
@@ -201,18 +175,7 @@ impl LoaderAndRunner {
             ));
         }
 
-        let options = if attrs[0].meta.require_list().is_err() {
-            Default::default()
-        } else {
-            attrs[0].parse_args()?
-        };
-
-        let builder = BuilderPattern {
-            path: attrs[0].path().clone(),
-            options,
-        };
-
-        Ok(builder)
+        BuilderPattern::from_attribute(&attrs[0])
     }
 }
 
@@ -400,32 +363,6 @@ mod tests {
         }
     }
 
-    #[test]
-    fn parse_builder_options() {
-        let input: BuilderOptions = parse_quote!(
-            string = "string_val",
-            boolean = true,
-            integer = 5,
-            float = 2.65,
-            enum_variant = SomeEnum::Variant1,
-            sensitive = "user:{secrets.password}"
-        );
-
-        let mut expected: BuilderOptions = Default::default();
-        expected.options.push(parse_quote!(string = "string_val"));
-        expected.options.push(parse_quote!(boolean = true));
-        expected.options.push(parse_quote!(integer = 5));
-        expected.options.push(parse_quote!(float = 2.65));
-        expected
-            .options
-            .push(parse_quote!(enum_variant = SomeEnum::Variant1));
-        expected
-            .options
-            .push(parse_quote!(sensitive = "user:{secrets.password}"));
-
-        assert_eq!(input, expected);
-    }
-
     #[test]
     fn parse_input_with_options() {
         let mut input = parse_quote!(
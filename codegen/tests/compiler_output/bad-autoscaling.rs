@@ -0,0 +1,2 @@
+#[shuttle_codegen::main(min_replicas = 4, max_replicas = 2)]
+async fn bad_autoscaling() -> ShuttleRocket {}
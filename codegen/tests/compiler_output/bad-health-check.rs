@@ -0,0 +1,2 @@
+#[shuttle_codegen::main(health_check_path = "health", shutdown_timeout = 30)]
+async fn bad_health_check() -> ShuttleRocket {}
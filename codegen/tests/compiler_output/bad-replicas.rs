@@ -0,0 +1,2 @@
+#[shuttle_codegen::main(replicas = 0)]
+async fn bad_replicas() -> ShuttleRocket {}
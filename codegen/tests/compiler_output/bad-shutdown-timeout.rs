@@ -0,0 +1,2 @@
+#[shuttle_codegen::main(instance_size = "m", shutdown_timeout = 86400)]
+async fn bad_shutdown_timeout() -> ShuttleRocket {}
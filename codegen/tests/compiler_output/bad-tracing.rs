@@ -0,0 +1,2 @@
+#[shuttle_codegen::main(tracing = "setup")]
+async fn bad_tracing() -> ShuttleRocket {}
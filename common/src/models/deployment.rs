@@ -96,6 +96,8 @@ pub struct DeploymentResponse {
     pub build_meta: Option<BuildMeta>,
     pub redeployment_of: Option<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub annotations: Option<HashMap<String, String>>,
 }
 
 #[cfg(feature = "display")]
@@ -110,12 +112,24 @@ impl DeploymentResponse {
     }
     pub fn to_string_colored(&self) -> String {
         // TODO: make this look nicer
-        format!(
+        let mut s = format!(
             "Deployment {} - {}\n{}",
             self.id.as_str().bold(),
             self.state.to_string_colored(),
             self.uris.join("\n"),
-        )
+        );
+        if let Some(ref description) = self.description {
+            s.push_str(&format!("\nMessage: {description}"));
+        }
+        if let Some(ref annotations) = self.annotations {
+            let mut annotations = annotations.iter().collect::<Vec<_>>();
+            annotations.sort();
+            for (k, v) in annotations {
+                s.push_str(&format!("\n{}: {v}", k.as_str().dim()));
+            }
+        }
+
+        s
     }
 }
 
@@ -136,7 +150,7 @@ pub enum DeploymentRequest {
     BuildArchive(Box<DeploymentRequestBuildArchive>),
     // TODO?: Add GitRepo(DeploymentRequestGitRepo)
     /// Use this image directly. Can be used to skip the build step.
-    Image(DeploymentRequestImage),
+    Image(Box<DeploymentRequestImage>),
     //
     // No Unknown variant: is a Request type and should only be deserialized on backend
 }
@@ -153,6 +167,12 @@ pub struct DeploymentRequestBuildArchive {
     pub secrets: Option<HashMap<String, String>>,
     pub build_meta: Option<BuildMeta>,
     pub infra: Option<InfraRequest>,
+    /// Reason for this deployment, stored as the deployment's description
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Free-form key-value annotations, such as ticket IDs or CI run URLs
+    #[serde(default)]
+    pub annotations: Option<HashMap<String, String>>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -185,6 +205,22 @@ pub struct BuildArgsRust {
     pub no_default_features: bool,
     /// Use the mold linker
     pub mold: bool,
+    /// Use BuildKit cache mounts for the cargo registry, git checkouts and target directory
+    #[serde(default)]
+    pub cache_mounts: bool,
+    /// Wrap rustc with sccache, which implies `cache_mounts` so that its cache is persisted
+    #[serde(default)]
+    pub sccache: bool,
+    /// Base image for the final stage of the build
+    #[serde(default)]
+    pub runtime_image: RuntimeImage,
+}
+
+impl BuildArgsRust {
+    /// Whether the build uses cache mounts, which sccache needs to persist its cache
+    pub fn uses_cache_mounts(&self) -> bool {
+        self.cache_mounts || self.sccache
+    }
 }
 
 impl Default for BuildArgsRust {
@@ -198,10 +234,54 @@ impl Default for BuildArgsRust {
             features: Default::default(),
             no_default_features: Default::default(),
             mold: Default::default(),
+            cache_mounts: Default::default(),
+            sccache: Default::default(),
+            runtime_image: Default::default(),
         }
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Display, EnumString, Eq, PartialEq, Serialize,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[typeshare::typeshare]
+pub enum RuntimeImage {
+    /// Debian slim with ca-certificates and curl installed
+    #[default]
+    Default,
+    /// Plain Debian slim
+    DebianSlim,
+    /// Distroless image with glibc and ca-certificates, without a shell
+    Distroless,
+    /// Fully static musl binary on an empty image
+    StaticMusl,
+    //
+    // No Unknown variant: is a Request type and should only be deserialized on backend
+}
+
+impl RuntimeImage {
+    /// The cargo target to compile for, if not the default target of the builder
+    pub fn cargo_target(&self) -> Option<&'static str> {
+        match self {
+            Self::StaticMusl => Some("x86_64-unknown-linux-musl"),
+            _ => None,
+        }
+    }
+
+    /// Whether the image has bash, which is needed to run `shuttle_setup_container.sh`
+    pub fn has_shell(&self) -> bool {
+        matches!(self, Self::Default | Self::DebianSlim)
+    }
+
+    /// Whether the image ships a CA bundle, otherwise it is copied from the builder
+    pub fn has_ca_certificates(&self) -> bool {
+        matches!(self, Self::Default | Self::Distroless)
+    }
+}
+
 /// Max length of strings in the git metadata
 pub const GIT_STRINGS_MAX_LENGTH: usize = 80;
 
@@ -239,7 +319,26 @@ pub struct DeploymentRequestImage {
     pub image: String,
     /// TODO: Remove this in favour of a separate secrets uploading action.
     pub secrets: Option<HashMap<String, String>>,
-    // TODO: credentials fields for private repos??
+    /// Credentials for pulling the image from a private registry
+    #[serde(default)]
+    pub registry_credentials: Option<RegistryCredentials>,
+    /// Reason for this deployment, stored as the deployment's description
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Free-form key-value annotations, such as ticket IDs or CI run URLs
+    #[serde(default)]
+    pub annotations: Option<HashMap<String, String>>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[typeshare::typeshare]
+pub struct RegistryCredentials {
+    /// Registry host the credentials are for, e.g. `ghcr.io`
+    pub registry: String,
+    pub username: String,
+    /// Password or access token
+    pub password: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -303,6 +402,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn runtime_image_from_and_to_str() {
+        assert_eq!(
+            RuntimeImage::StaticMusl,
+            RuntimeImage::from_str("static-musl").unwrap()
+        );
+        assert_eq!(RuntimeImage::DebianSlim.to_string(), "debian-slim");
+        assert_eq!(
+            serde_json::to_string(&RuntimeImage::Distroless).unwrap(),
+            r#""distroless""#
+        );
+    }
+
     #[test]
     fn env_from_str() {
         assert_eq!(Environment::Local, Environment::from_str("local").unwrap());
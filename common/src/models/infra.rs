@@ -8,4 +8,54 @@ use super::project::ComputeTier;
 pub struct InfraRequest {
     pub instance_size: Option<ComputeTier>,
     pub replicas: Option<u8>,
+    /// Path that responds with a success status when the service is ready to receive traffic
+    pub health_check_path: Option<String>,
+    /// Lower bound of replicas when autoscaling
+    pub min_replicas: Option<u8>,
+    /// Upper bound of replicas when autoscaling
+    pub max_replicas: Option<u8>,
+    /// Seconds to wait for the service to stop gracefully before it is killed
+    pub shutdown_timeout: Option<u16>,
+    /// Resources declared on the arguments of the main function
+    pub resources: Option<Vec<ResourceDeclaration>>,
+}
+
+/// A resource declared with an attribute on an argument of the main function,
+/// such as `#[shuttle_shared_db::Postgres(local_uri = "...")] pool: PgPool`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[typeshare::typeshare]
+pub struct ResourceDeclaration {
+    /// Path of the attribute, such as `shuttle_shared_db::Postgres`
+    pub path: String,
+    /// Name of the argument the resource is given to
+    pub argument: String,
+    /// Options set in the attribute, in the order they are written
+    pub options: Vec<ResourceOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[typeshare::typeshare]
+pub struct ResourceOption {
+    pub name: String,
+    /// The value as written in the code
+    pub value: String,
+}
+
+impl ResourceDeclaration {
+    /// The declaration as it is written in the attribute, such as `shuttle_shared_db::Postgres(local_uri = "...")`
+    pub fn to_attribute_string(&self) -> String {
+        if self.options.is_empty() {
+            return self.path.clone();
+        }
+        let options = self
+            .options
+            .iter()
+            .map(|o| format!("{} = {}", o.name, o.value))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("{}({options})", self.path)
+    }
 }
@@ -115,6 +115,17 @@ pub struct ProjectUpdateRequest {
     pub config: Option<serde_json::Value>,
 }
 
+/// Set wanted field(s) to Some to change the scale of the project
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[typeshare::typeshare]
+pub struct ProjectScaleRequest {
+    /// Instance size of the project's deployments
+    pub compute_tier: Option<ComputeTier>,
+    /// Number of instances to run
+    pub replicas: Option<u8>,
+}
+
 #[derive(
     Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, EnumString,
 )]
@@ -211,6 +222,26 @@ pub struct ProjectUsageDaily {
     pub runtime_minutes: u32,
 }
 
+impl ProjectUsageDaily {
+    /// Average CPU utilisation as a fraction of the highest reserved CPU that day
+    pub fn cpu_utilisation(&self) -> f32 {
+        utilisation(self.avg_cpu_utilised, self.max_cpu_reserved)
+    }
+
+    /// Average memory utilisation as a fraction of the highest reserved memory that day
+    pub fn memory_utilisation(&self) -> f32 {
+        utilisation(self.avg_mem_utilised, self.max_mem_reserved)
+    }
+}
+
+fn utilisation(used: f32, reserved: f32) -> f32 {
+    if reserved > 0.0 {
+        (used / reserved).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[typeshare::typeshare]
@@ -2,6 +2,11 @@ use std::borrow::Cow;
 
 use serde::{Deserialize, Serialize};
 
+#[cfg(feature = "display")]
+use crossterm::style::Stylize;
+#[cfg(feature = "display")]
+use std::fmt::Write;
+
 const fn default_betterstack_host() -> Cow<'static, str> {
     Cow::Borrowed("in-otel.logs.betterstack.com")
 }
@@ -16,7 +21,7 @@ const fn default_logfire_host() -> Cow<'static, str> {
 #[typeshare::typeshare]
 pub struct TelemetrySinkStatus {
     /// Indicates that the associated project is configured to export telemetry data to this sink
-    enabled: bool,
+    pub enabled: bool,
 }
 
 /// A safe-for-display representation of the current telemetry export configuration for a given project
@@ -24,11 +29,41 @@ pub struct TelemetrySinkStatus {
 #[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
 #[typeshare::typeshare]
 pub struct TelemetryConfigResponse {
-    betterstack: Option<TelemetrySinkStatus>,
-    datadog: Option<TelemetrySinkStatus>,
-    grafana_cloud: Option<TelemetrySinkStatus>,
-    logfire: Option<TelemetrySinkStatus>,
-    generic: Option<TelemetrySinkStatus>,
+    pub betterstack: Option<TelemetrySinkStatus>,
+    pub datadog: Option<TelemetrySinkStatus>,
+    pub grafana_cloud: Option<TelemetrySinkStatus>,
+    pub logfire: Option<TelemetrySinkStatus>,
+    pub generic: Option<TelemetrySinkStatus>,
+}
+
+impl TelemetryConfigResponse {
+    /// Display names of the sinks and whether telemetry is exported to them
+    pub fn sinks(&self) -> [(&'static str, bool); 5] {
+        let enabled = |s: &Option<TelemetrySinkStatus>| s.as_ref().is_some_and(|s| s.enabled);
+        [
+            ("Betterstack", enabled(&self.betterstack)),
+            ("Datadog", enabled(&self.datadog)),
+            ("Grafana Cloud", enabled(&self.grafana_cloud)),
+            ("Logfire", enabled(&self.logfire)),
+            ("Generic OTel", enabled(&self.generic)),
+        ]
+    }
+
+    #[cfg(feature = "display")]
+    pub fn to_string_colored(&self) -> String {
+        let mut s = String::new();
+        writeln!(&mut s, "{}", "Telemetry sinks:".bold()).unwrap();
+        for (name, enabled) in self.sinks() {
+            let state = if enabled {
+                "enabled".green()
+            } else {
+                "disabled".dark_grey()
+            };
+            writeln!(&mut s, "  {name:<14} {state}").unwrap();
+        }
+
+        s
+    }
 }
 
 impl From<Vec<TelemetrySinkConfig>> for TelemetryConfigResponse {
@@ -1,4 +1,6 @@
-use chrono::{DateTime, Local, SecondsFormat};
+use std::{cmp::Ordering, collections::BTreeSet};
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
 use comfy_table::{
     presets::{NOTHING, UTF8_BORDERS_ONLY},
     Attribute, Cell, Color, ContentArrangement, Table,
@@ -7,94 +9,513 @@ use comfy_table::{
 use crate::{
     models::{
         certificate::CertificateResponse,
-        deployment::DeploymentResponse,
-        project::ProjectResponse,
+        deployment::{BuildMeta, DeploymentResponse},
+        infra::ResourceDeclaration,
+        project::{ProjectResponse, ProjectUsageDaily},
         resource::{ResourceResponse, ResourceType},
+        team::{TeamInvite, TeamMembership, TeamResponse},
     },
     secrets::SecretStore,
     DatabaseInfo,
 };
 
-pub fn get_certificates_table(certs: &[CertificateResponse], raw: bool) -> String {
+/// Column selection, sorting and styling for tables
+#[derive(Clone, Debug, Default)]
+pub struct TableOptions {
+    /// Output tables without borders
+    pub raw: bool,
+    /// Keys of the columns to show, in order. All columns are shown if empty.
+    pub columns: Vec<String>,
+    pub sort: Option<TableSort>,
+    pub no_header: bool,
+}
+
+/// Sort the rows by the values of a column
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableSort {
+    pub column: String,
+    pub descending: bool,
+}
+
+/// A column key that does not exist in a table
+#[derive(Debug)]
+pub struct UnknownColumn {
+    pub column: String,
+    pub expected: Vec<&'static str>,
+}
+
+impl std::fmt::Display for UnknownColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown table column '{}', expected one of: {}",
+            self.column,
+            self.expected.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for UnknownColumn {}
+
+fn new_table<'a>(options: &TableOptions, header: impl IntoIterator<Item = &'a str>) -> Table {
     let mut table = Table::new();
     table
-        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
-        .set_content_arrangement(ContentArrangement::Disabled)
-        .set_header(vec!["Certificate ID", "Subject", "Expires"]);
+        .load_preset(if options.raw {
+            NOTHING
+        } else {
+            UTF8_BORDERS_ONLY
+        })
+        .set_content_arrangement(ContentArrangement::Disabled);
+    if !options.no_header {
+        table.set_header(header);
+    }
+
+    table
+}
 
-    for cert in certs {
-        table.add_row(vec![
-            Cell::new(&cert.id).add_attribute(Attribute::Bold),
-            Cell::new(&cert.subject),
-            Cell::new(&cert.not_after),
-        ]);
+/// A column of a table with one row per item
+struct Column<'a, T> {
+    key: &'static str,
+    header: &'static str,
+    cell: Box<dyn Fn(&T) -> Cell + 'a>,
+    /// Sorts by the displayed text if not set
+    sort_key: Option<SortKeyFn<'a, T>>,
+}
+
+type SortKeyFn<'a, T> = Box<dyn Fn(&T) -> SortKey + 'a>;
+
+/// A typed value to sort a column by, for columns where the displayed text does not sort correctly
+#[derive(PartialEq, PartialOrd)]
+enum SortKey {
+    Number(f64),
+    Time(DateTime<Utc>),
+    Text(String),
+}
+
+fn column<'a, T>(
+    key: &'static str,
+    header: &'static str,
+    cell: impl Fn(&T) -> Cell + 'a,
+) -> Column<'a, T> {
+    Column {
+        key,
+        header,
+        cell: Box::new(cell),
+        sort_key: None,
     }
+}
 
-    table.to_string()
+impl<'a, T> Column<'a, T> {
+    fn sort_by(mut self, sort_key: impl Fn(&T) -> SortKey + 'a) -> Self {
+        self.sort_key = Some(Box::new(sort_key));
+        self
+    }
+
+    fn sort_key(&self, item: &T) -> SortKey {
+        match self.sort_key {
+            Some(ref sort_key) => sort_key(item),
+            None => SortKey::Text((self.cell)(item).content()),
+        }
+    }
 }
 
-pub fn deployments_table(deployments: &[DeploymentResponse], raw: bool) -> String {
-    let mut table = Table::new();
-    table
-        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
-        .set_content_arrangement(ContentArrangement::Disabled)
-        .set_header(vec!["Deployment ID", "Status", "Date", "Git revision"]);
-
-    for deploy in deployments.iter() {
-        let datetime: DateTime<Local> = DateTime::from(deploy.created_at);
-        table.add_row(vec![
-            Cell::new(&deploy.id).add_attribute(Attribute::Bold),
-            Cell::new(&deploy.state).fg(deploy.state.get_color_comfy_table()),
-            Cell::new(datetime.to_rfc3339_opts(SecondsFormat::Secs, false)),
-            Cell::new(
-                deploy
-                    .build_meta
-                    .as_ref()
-                    .map(ToString::to_string)
-                    .unwrap_or_default(),
-            ),
-        ]);
+fn list_table<T>(
+    items: &[T],
+    columns: &[Column<T>],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    let find = |key: &str| {
+        columns
+            .iter()
+            .find(|c| c.key == key)
+            .ok_or_else(|| UnknownColumn {
+                column: key.to_owned(),
+                expected: columns.iter().map(|c| c.key).collect(),
+            })
+    };
+    let selected = if options.columns.is_empty() {
+        columns.iter().collect()
+    } else {
+        options
+            .columns
+            .iter()
+            .map(|key| find(key))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+
+    let mut items = items.iter().collect::<Vec<_>>();
+    if let Some(ref sort) = options.sort {
+        let column = find(&sort.column)?;
+        let mut keyed = items
+            .into_iter()
+            .map(|item| (column.sort_key(item), item))
+            .collect::<Vec<_>>();
+        keyed.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        if sort.descending {
+            keyed.reverse();
+        }
+        items = keyed.into_iter().map(|(_, item)| item).collect();
     }
 
-    table.to_string()
+    let mut table = new_table(options, selected.iter().map(|c| c.header));
+    for item in items {
+        table.add_row(selected.iter().map(|c| (c.cell)(item)));
+    }
+
+    Ok(table.to_string())
 }
 
-pub fn get_projects_table(projects: &[ProjectResponse], raw: bool) -> String {
-    let mut table = Table::new();
-    table
-        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
-        .set_content_arrangement(ContentArrangement::Disabled)
-        .set_header(vec!["Project ID", "Project Name", "Deployment Status"]);
-
-    for project in projects {
-        let state = project
-            .deployment_state
-            .as_ref()
-            .map(|s| s.to_string())
-            .unwrap_or_default();
-        let color = project
-            .deployment_state
-            .as_ref()
-            .map(|s| s.get_color_comfy_table())
-            .unwrap_or(Color::White);
-        table.add_row(vec![
-            Cell::new(&project.id).add_attribute(Attribute::Bold),
-            Cell::new(&project.name),
-            Cell::new(state).fg(color),
-        ]);
+pub fn get_certificates_table(
+    certs: &[CertificateResponse],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        certs,
+        &[
+            column("id", "Certificate ID", |c: &CertificateResponse| {
+                Cell::new(&c.id).add_attribute(Attribute::Bold)
+            }),
+            column("subject", "Subject", |c: &CertificateResponse| {
+                Cell::new(&c.subject)
+            }),
+            column("not_after", "Expires", |c: &CertificateResponse| {
+                Cell::new(&c.not_after)
+            }),
+        ],
+        options,
+    )
+}
+
+pub fn deployments_table(
+    deployments: &[DeploymentResponse],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        deployments,
+        &[
+            column("id", "Deployment ID", |d: &DeploymentResponse| {
+                Cell::new(&d.id).add_attribute(Attribute::Bold)
+            }),
+            column("state", "Status", |d: &DeploymentResponse| {
+                Cell::new(&d.state).fg(d.state.get_color_comfy_table())
+            }),
+            column("created_at", "Date", |d: &DeploymentResponse| {
+                let datetime: DateTime<Local> = DateTime::from(d.created_at);
+                Cell::new(datetime.to_rfc3339_opts(SecondsFormat::Secs, false))
+            })
+            .sort_by(|d: &DeploymentResponse| SortKey::Time(d.created_at)),
+            column("build_meta", "Git revision", |d: &DeploymentResponse| {
+                Cell::new(
+                    d.build_meta
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                )
+            }),
+            column("message", "Message", |d: &DeploymentResponse| {
+                Cell::new(deployment_message(d))
+            }),
+        ],
+        options,
+    )
+}
+
+/// The description followed by the annotations, one per line
+fn deployment_message(deployment: &DeploymentResponse) -> String {
+    let mut lines = deployment.description.iter().cloned().collect::<Vec<_>>();
+    if let Some(ref annotations) = deployment.annotations {
+        let mut annotations = annotations
+            .iter()
+            .map(|(k, v)| format!("{k}={v}"))
+            .collect::<Vec<_>>();
+        annotations.sort();
+        lines.extend(annotations);
     }
 
+    lines.join("\n")
+}
+
+/// Compares the state, git metadata and URIs of two deployments.
+/// Only the fields that differ are shown, except for the date and git commit.
+/// The infra, build args, secrets and resources are not part of [`DeploymentResponse`], so they can't be compared.
+///
+/// The rows are fields rather than items, so column selection and sorting do not apply.
+pub fn deployment_diff_table(
+    a: &DeploymentResponse,
+    b: &DeploymentResponse,
+    options: &TableOptions,
+) -> String {
+    let mut table = new_table(options, ["", a.id.as_str(), b.id.as_str()]);
+
+    let mut row = |name: &str, a: String, b: String, always: bool| {
+        if always || a != b {
+            let color = if a == b { Color::Reset } else { Color::Yellow };
+            table.add_row(vec![
+                Cell::new(name).add_attribute(Attribute::Bold),
+                Cell::new(a).fg(color),
+                Cell::new(b).fg(color),
+            ]);
+        }
+    };
+    let unknown = || "(unknown)".to_owned();
+
+    row("Status", a.state.to_string(), b.state.to_string(), false);
+    let date = |d: &DeploymentResponse| {
+        DateTime::<Local>::from(d.created_at).to_rfc3339_opts(SecondsFormat::Secs, false)
+    };
+    row("Date", date(a), date(b), true);
+
+    let meta = |d: &DeploymentResponse, f: fn(&BuildMeta) -> Option<String>| {
+        d.build_meta.as_ref().and_then(f).unwrap_or_else(unknown)
+    };
+    row(
+        "Git commit",
+        meta(a, |m| m.git_commit_id.clone()),
+        meta(b, |m| m.git_commit_id.clone()),
+        true,
+    );
+    row(
+        "Git branch",
+        meta(a, |m| m.git_branch.clone()),
+        meta(b, |m| m.git_branch.clone()),
+        false,
+    );
+    row(
+        "Git message",
+        meta(a, |m| m.git_commit_msg.clone()),
+        meta(b, |m| m.git_commit_msg.clone()),
+        false,
+    );
+    row(
+        "Git dirty",
+        meta(a, |m| m.git_dirty.map(|d| d.to_string())),
+        meta(b, |m| m.git_dirty.map(|d| d.to_string())),
+        false,
+    );
+
+    // URIs are shown in full when they differ, marking the ones that were removed and added
+    let set = |d: &DeploymentResponse| d.uris.iter().cloned().collect::<BTreeSet<_>>();
+    let (uris_a, uris_b) = (set(a), set(b));
+    let show = |s: &BTreeSet<String>, other: &BTreeSet<String>, mark: &str| {
+        s.iter()
+            .map(|k| {
+                if other.contains(k) {
+                    k.clone()
+                } else {
+                    format!("{mark} {k}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    row(
+        "URIs",
+        show(&uris_a, &uris_b, "-"),
+        show(&uris_b, &uris_a, "+"),
+        false,
+    );
+
     table.to_string()
 }
 
+pub fn get_projects_table(
+    projects: &[ProjectResponse],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        projects,
+        &[
+            column("id", "Project ID", |p: &ProjectResponse| {
+                Cell::new(&p.id).add_attribute(Attribute::Bold)
+            }),
+            column("name", "Project Name", |p: &ProjectResponse| {
+                Cell::new(&p.name)
+            }),
+            column(
+                "deployment_state",
+                "Deployment Status",
+                |p: &ProjectResponse| {
+                    let state = p
+                        .deployment_state
+                        .as_ref()
+                        .map(|s| s.to_string())
+                        .unwrap_or_default();
+                    let color = p
+                        .deployment_state
+                        .as_ref()
+                        .map(|s| s.get_color_comfy_table())
+                        .unwrap_or(Color::White);
+                    Cell::new(state).fg(color)
+                },
+            ),
+        ],
+        options,
+    )
+}
+
+const SPARK_BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One bar per value, for values between 0 and 1
+pub fn sparkline(values: impl IntoIterator<Item = f32>) -> String {
+    values
+        .into_iter()
+        .map(|v| SPARK_BARS[(v.clamp(0.0, 1.0) * (SPARK_BARS.len() - 1) as f32).round() as usize])
+        .collect()
+}
+
+/// A bar and percentage for a value between 0 and 1
+fn utilisation_cell(v: f32) -> Cell {
+    let color = match v {
+        v if v >= 0.9 => Color::Red,
+        v if v >= 0.7 => Color::Yellow,
+        _ => Color::Green,
+    };
+    Cell::new(format!("{} {:>3.0}%", sparkline([v]), v * 100.0)).fg(color)
+}
+
+pub fn get_usage_daily_table(
+    daily: &[ProjectUsageDaily],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        daily,
+        &[
+            column("isodate", "Date", |d: &ProjectUsageDaily| {
+                Cell::new(d.isodate).add_attribute(Attribute::Bold)
+            }),
+            column("build_minutes", "Build min", |d: &ProjectUsageDaily| {
+                Cell::new(d.build_minutes)
+            })
+            .sort_by(|d: &ProjectUsageDaily| SortKey::Number(d.build_minutes.into())),
+            column("runtime_minutes", "Runtime min", |d: &ProjectUsageDaily| {
+                Cell::new(d.runtime_minutes)
+            })
+            .sort_by(|d: &ProjectUsageDaily| SortKey::Number(d.runtime_minutes.into())),
+            column(
+                "reserved_vcpu_hours",
+                "Reserved vCPU h",
+                |d: &ProjectUsageDaily| Cell::new(format!("{:.2}", d.reserved_vcpu_hours)),
+            )
+            .sort_by(|d: &ProjectUsageDaily| SortKey::Number(d.reserved_vcpu_hours.into())),
+            column(
+                "billable_vcpu_hours",
+                "Billable vCPU h",
+                |d: &ProjectUsageDaily| Cell::new(format!("{:.2}", d.billable_vcpu_hours)),
+            )
+            .sort_by(|d: &ProjectUsageDaily| SortKey::Number(d.billable_vcpu_hours.into())),
+            column("cpu", "CPU", |d: &ProjectUsageDaily| {
+                utilisation_cell(d.cpu_utilisation())
+            })
+            .sort_by(|d: &ProjectUsageDaily| SortKey::Number(d.cpu_utilisation().into())),
+            column("memory", "Memory", |d: &ProjectUsageDaily| {
+                utilisation_cell(d.memory_utilisation())
+            })
+            .sort_by(|d: &ProjectUsageDaily| SortKey::Number(d.memory_utilisation().into())),
+        ],
+        options,
+    )
+}
+
+pub fn get_infra_resources_table(
+    resources: &[ResourceDeclaration],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        resources,
+        &[
+            column("path", "Resource", |r: &ResourceDeclaration| {
+                Cell::new(&r.path).add_attribute(Attribute::Bold)
+            }),
+            column("argument", "Argument", |r: &ResourceDeclaration| {
+                Cell::new(&r.argument)
+            }),
+            column("options", "Options", |r: &ResourceDeclaration| {
+                Cell::new(
+                    r.options
+                        .iter()
+                        .map(|o| format!("{} = {}", o.name, o.value))
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                )
+            }),
+        ],
+        options,
+    )
+}
+
+pub fn get_teams_table(
+    teams: &[TeamResponse],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        teams,
+        &[
+            column("id", "Team ID", |t: &TeamResponse| {
+                Cell::new(&t.id).add_attribute(Attribute::Bold)
+            }),
+            column("name", "Team Name", |t: &TeamResponse| Cell::new(&t.name)),
+            column("role", "Your Role", |t: &TeamResponse| {
+                Cell::new(&t.membership.role)
+            }),
+        ],
+        options,
+    )
+}
+
+pub fn get_team_members_table(
+    members: &[TeamMembership],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        members,
+        &[
+            column("user_id", "User ID", |m: &TeamMembership| {
+                Cell::new(&m.user_id).add_attribute(Attribute::Bold)
+            }),
+            column("nickname", "Name", |m: &TeamMembership| {
+                Cell::new(m.nickname.as_deref().unwrap_or_default())
+            }),
+            column("email", "Email", |m: &TeamMembership| {
+                Cell::new(m.email.as_deref().unwrap_or_default())
+            }),
+            column("role", "Role", |m: &TeamMembership| Cell::new(&m.role)),
+        ],
+        options,
+    )
+}
+
+pub fn get_team_invites_table(
+    invites: &[TeamInvite],
+    options: &TableOptions,
+) -> Result<String, UnknownColumn> {
+    list_table(
+        invites,
+        &[
+            column("id", "Invite ID", |i: &TeamInvite| {
+                Cell::new(&i.id).add_attribute(Attribute::Bold)
+            }),
+            column("email", "Email", |i: &TeamInvite| Cell::new(&i.email)),
+            column("role", "Role", |i: &TeamInvite| Cell::new(&i.role)),
+            column("expires_at", "Expires", |i: &TeamInvite| {
+                let datetime: DateTime<Local> = DateTime::from(i.expires_at);
+                Cell::new(datetime.to_rfc3339_opts(SecondsFormat::Secs, false))
+            })
+            .sort_by(|i: &TeamInvite| SortKey::Time(i.expires_at)),
+        ],
+        options,
+    )
+}
+
+/// Column selection and sorting apply to the databases table.
+/// The secrets table only has keys, so callers should reject column selection when there are secrets.
 pub fn get_resource_tables(
     resources: &[ResourceResponse],
     service_name: &str,
-    raw: bool,
+    options: &TableOptions,
     show_secrets: bool,
-) -> String {
+) -> Result<String, UnknownColumn> {
     if resources.is_empty() {
-        return "No resources are linked to this service\n".to_string();
+        return Ok("No resources are linked to this service\n".to_string());
     }
     let mut output = Vec::new();
     output.push(get_secrets_table(
@@ -104,7 +525,7 @@ pub fn get_resource_tables(
             .map(Clone::clone)
             .collect::<Vec<_>>(),
         service_name,
-        raw,
+        options,
     ));
     output.push(get_databases_table(
         &resources
@@ -121,35 +542,43 @@ pub fn get_resource_tables(
             .map(Clone::clone)
             .collect::<Vec<_>>(),
         service_name,
-        raw,
+        options,
         show_secrets,
-    ));
-    output.join("\n")
+    )?);
+
+    Ok(output.join("\n"))
 }
 
 fn get_databases_table(
     databases: &[ResourceResponse],
     service_name: &str,
-    raw: bool,
+    options: &TableOptions,
     show_secrets: bool,
-) -> String {
+) -> Result<String, UnknownColumn> {
     if databases.is_empty() {
-        return String::new();
+        return Ok(String::new());
     }
 
-    let mut table = Table::new();
-    table
-        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
-        .set_content_arrangement(ContentArrangement::Disabled)
-        .set_header(vec!["Type", "Connection string"]);
-
-    for database in databases {
-        let connection_string = serde_json::from_value::<DatabaseInfo>(database.output.clone())
-            .expect("resource data to be a valid database")
-            .connection_string(show_secrets);
-
-        table.add_row(vec![database.r#type.to_string(), connection_string]);
-    }
+    let table = list_table(
+        databases,
+        &[
+            column("type", "Type", |d: &ResourceResponse| {
+                Cell::new(d.r#type.to_string())
+            }),
+            column(
+                "connection_string",
+                "Connection string",
+                |d: &ResourceResponse| {
+                    Cell::new(
+                        serde_json::from_value::<DatabaseInfo>(d.output.clone())
+                            .expect("resource data to be a valid database")
+                            .connection_string(show_secrets),
+                    )
+                },
+            ),
+        ],
+        options,
+    )?;
 
     let show_secret_hint = if databases.is_empty() || show_secrets {
         ""
@@ -157,10 +586,16 @@ fn get_databases_table(
         "Hint: you can show the secrets of these resources using `shuttle resource list --show-secrets`\n"
     };
 
-    format!("These databases are linked to {service_name}\n{table}\n{show_secret_hint}")
+    Ok(format!(
+        "These databases are linked to {service_name}\n{table}\n{show_secret_hint}"
+    ))
 }
 
-fn get_secrets_table(secrets: &[ResourceResponse], service_name: &str, raw: bool) -> String {
+fn get_secrets_table(
+    secrets: &[ResourceResponse],
+    service_name: &str,
+    options: &TableOptions,
+) -> String {
     let Some(secrets) = secrets.first() else {
         return String::new();
     };
@@ -169,15 +604,128 @@ fn get_secrets_table(secrets: &[ResourceResponse], service_name: &str, raw: bool
         return String::new();
     }
 
-    let mut table = Table::new();
-    table
-        .load_preset(if raw { NOTHING } else { UTF8_BORDERS_ONLY })
-        .set_content_arrangement(ContentArrangement::Disabled)
-        .set_header(vec!["Key"]);
-
+    let mut table = new_table(options, ["Key"]);
     for key in secrets.secrets.keys() {
         table.add_row(vec![key]);
     }
 
     format!("These secrets can be accessed by {service_name}\n{table}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn projects() -> Vec<ProjectResponse> {
+        ["b-app", "a-app", "c-app"]
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                serde_json::from_value(serde_json::json!({
+                    "id": format!("proj_{i}"),
+                    "name": name,
+                    "user_id": "user_1",
+                    "team_id": null,
+                    "created_at": "2025-01-01T00:00:00Z",
+                    "compute_tier": null,
+                    "deployment_state": null,
+                    "uris": [],
+                }))
+                .unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn select_and_sort_columns() {
+        let options = TableOptions {
+            raw: true,
+            columns: vec!["name".to_owned(), "id".to_owned()],
+            sort: Some(TableSort {
+                column: "name".to_owned(),
+                descending: true,
+            }),
+            no_header: true,
+        };
+        let table = get_projects_table(&projects(), &options).unwrap();
+        let rows = table
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            rows,
+            [
+                ["c-app", "proj_2"],
+                ["b-app", "proj_0"],
+                ["a-app", "proj_1"]
+            ]
+        );
+
+        let table = get_projects_table(&projects(), &TableOptions::default()).unwrap();
+        assert!(table.contains("Project Name"));
+        assert!(table.contains("Deployment Status"));
+    }
+
+    #[test]
+    fn sort_by_typed_key() {
+        let daily = [9, 10, 100]
+            .into_iter()
+            .map(|minutes| {
+                serde_json::from_value(serde_json::json!({
+                    "avg_cpu_utilised": 0.0,
+                    "avg_mem_utilised": 0.0,
+                    "billable_vcpu_hours": 0.0,
+                    "build_minutes": minutes,
+                    "isodate": "2025-01-01",
+                    "max_cpu_reserved": 0.0,
+                    "max_mem_reserved": 0.0,
+                    "min_cpu_reserved": 0.0,
+                    "min_mem_reserved": 0.0,
+                    "reserved_vcpu_hours": 0.0,
+                    "runtime_minutes": 0,
+                }))
+                .unwrap()
+            })
+            .collect::<Vec<ProjectUsageDaily>>();
+        let options = TableOptions {
+            raw: true,
+            columns: vec!["build_minutes".to_owned()],
+            sort: Some(TableSort {
+                column: "build_minutes".to_owned(),
+                descending: true,
+            }),
+            no_header: true,
+        };
+        let table = get_usage_daily_table(&daily, &options).unwrap();
+        let rows = table.split_whitespace().collect::<Vec<_>>();
+        assert_eq!(rows, ["100", "10", "9"]);
+    }
+
+    #[test]
+    fn sparklines() {
+        assert_eq!(sparkline([0.0, 0.5, 1.0, 2.0, -1.0]), "▁▅██▁");
+        assert_eq!(sparkline([]), "");
+    }
+
+    #[test]
+    fn unknown_columns() {
+        let options = TableOptions {
+            columns: vec!["state".to_owned()],
+            ..Default::default()
+        };
+        let err = get_projects_table(&projects(), &options).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown table column 'state', expected one of: id, name, deployment_state"
+        );
+
+        let options = TableOptions {
+            sort: Some(TableSort {
+                column: "created".to_owned(),
+                descending: false,
+            }),
+            ..Default::default()
+        };
+        assert!(deployments_table(&[], &options).is_err());
+    }
+}
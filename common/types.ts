@@ -68,6 +68,12 @@ export interface BuildArgsRust {
 	no_default_features: boolean;
 	/** Use the mold linker */
 	mold: boolean;
+	/** Use BuildKit cache mounts for the cargo registry, git checkouts and target directory */
+	cache_mounts?: boolean;
+	/** Wrap rustc with sccache, which implies `cache_mounts` so that its cache is persisted */
+	sccache?: boolean;
+	/** Base image for the final stage of the build */
+	runtime_image?: RuntimeImage;
 }
 
 export interface BuildMeta {
@@ -157,12 +163,24 @@ export interface DeploymentResponse {
 	build_meta?: BuildMeta;
 	redeployment_of?: string;
 	description?: string;
+	annotations?: Record<string, string>;
 }
 
 export interface DeploymentListResponse {
 	deployments: DeploymentResponse[];
 }
 
+export enum RuntimeImage {
+	/** Debian slim with ca-certificates and curl installed */
+	Default = "default",
+	/** Plain Debian slim */
+	DebianSlim = "debian-slim",
+	/** Distroless image with glibc and ca-certificates, without a shell */
+	Distroless = "distroless",
+	/** Fully static musl binary on an empty image */
+	StaticMusl = "static-musl",
+}
+
 export type BuildArgs = 
 	| { type: "Rust", content: BuildArgsRust };
 
@@ -178,6 +196,16 @@ export enum ComputeTier {
 export interface InfraRequest {
 	instance_size?: ComputeTier;
 	replicas?: number;
+	/** Path that responds with a success status when the service is ready to receive traffic */
+	health_check_path?: string;
+	/** Lower bound of replicas when autoscaling */
+	min_replicas?: number;
+	/** Upper bound of replicas when autoscaling */
+	max_replicas?: number;
+	/** Seconds to wait for the service to stop gracefully before it is killed */
+	shutdown_timeout?: number;
+	/** Resources declared on the arguments of the main function */
+	resources?: ResourceDeclaration[];
 }
 
 export interface DeploymentRequestBuildArchive {
@@ -191,12 +219,30 @@ export interface DeploymentRequestBuildArchive {
 	secrets?: Record<string, string>;
 	build_meta?: BuildMeta;
 	infra?: InfraRequest;
+	/** Reason for this deployment, stored as the deployment's description */
+	message?: string;
+	/** Free-form key-value annotations, such as ticket IDs or CI run URLs */
+	annotations?: Record<string, string>;
 }
 
 export interface DeploymentRequestImage {
 	image: string;
 	/** TODO: Remove this in favour of a separate secrets uploading action. */
 	secrets?: Record<string, string>;
+	/** Credentials for pulling the image from a private registry */
+	registry_credentials?: RegistryCredentials;
+	/** Reason for this deployment, stored as the deployment's description */
+	message?: string;
+	/** Free-form key-value annotations, such as ticket IDs or CI run URLs */
+	annotations?: Record<string, string>;
+}
+
+export interface RegistryCredentials {
+	/** Registry host the credentials are for, e.g. `ghcr.io` */
+	registry: string;
+	username: string;
+	/** Password or access token */
+	password: string;
 }
 
 export interface GenericOtelConfig {
@@ -326,6 +372,14 @@ export interface ProjectListResponse {
 	projects: ProjectResponse[];
 }
 
+/** Set wanted field(s) to Some to change the scale of the project */
+export interface ProjectScaleRequest {
+	/** Instance size of the project's deployments */
+	compute_tier?: ComputeTier;
+	/** Number of instances to run */
+	replicas?: number;
+}
+
 /** Set wanted field(s) to Some to update those parts of the project */
 export interface ProjectUpdateRequest {
 	/** Change display name */
@@ -380,6 +434,25 @@ export interface ProjectUsageResponse {
 	daily: ProjectUsageDaily[];
 }
 
+/**
+ * A resource declared with an attribute on an argument of the main function,
+ * such as `#[shuttle_shared_db::Postgres(local_uri = "...")] pool: PgPool`
+ */
+export interface ResourceDeclaration {
+	/** Path of the attribute, such as `shuttle_shared_db::Postgres` */
+	path: string;
+	/** Name of the argument the resource is given to */
+	argument: string;
+	/** Options set in the attribute, in the order they are written */
+	options: ResourceOption[];
+}
+
+export interface ResourceOption {
+	name: string;
+	/** The value as written in the code */
+	value: string;
+}
+
 export enum ResourceType {
 	DatabaseSharedPostgres = "database::shared::postgres",
 	DatabaseAwsRdsPostgres = "database::aws_rds::postgres",
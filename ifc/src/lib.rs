@@ -1,25 +1,53 @@
+mod resource;
+
+use std::fmt::Display;
+
 use proc_macro2::Span;
-use shuttle_common::models::infra::InfraRequest;
+use shuttle_common::models::infra::{InfraRequest, ResourceDeclaration};
 use syn::{
     meta::{parser, ParseNestedMeta},
     parse::Parser,
     parse_file, parse_quote,
     spanned::Spanned,
-    Attribute, Item, ItemFn, LitStr, Meta, MetaList, Path,
+    Attribute, Item, ItemFn, LitInt, LitStr, Meta, MetaList, Path,
 };
 
+pub use resource::{parse_resources_from_fn, BuilderOption, BuilderOptions, BuilderPattern};
+
 /// Takes rust source code and finds the `#[shuttle_runtime::main]`.
-/// Then, parses the attribute meta of that function and returns a map of string->string or null.
+/// Then, parses the attribute meta and the resource attributes of that function's arguments.
+/// Returns None if the attribute has no list, in which case the resources are not included either.
 pub fn parse_infra_from_code(rust_source_code: &str) -> Result<Option<InfraRequest>, syn::Error> {
-    let Some((_main_fn, main_attr)) = find_runtime_main_fn(rust_source_code)? else {
-        return Err(syn::Error::new(
+    let (main_fn, main_attr) = require_runtime_main_fn(rust_source_code)?;
+
+    let mut infra = parse_infra_from_meta(&main_attr.meta)?;
+    if let Some(ref mut infra) = infra {
+        let resources = parse_resources_from_fn(&main_fn)?;
+        if !resources.is_empty() {
+            infra.resources = Some(resources);
+        }
+    }
+
+    Ok(infra)
+}
+
+/// Takes rust source code and finds the `#[shuttle_runtime::main]`.
+/// Then, parses the resource attributes of that function's arguments.
+pub fn parse_resources_from_code(
+    rust_source_code: &str,
+) -> Result<Vec<ResourceDeclaration>, syn::Error> {
+    let (main_fn, _) = require_runtime_main_fn(rust_source_code)?;
+
+    parse_resources_from_fn(&main_fn)
+}
+
+fn require_runtime_main_fn(rust_source_code: &str) -> Result<(ItemFn, Attribute), syn::Error> {
+    find_runtime_main_fn(rust_source_code)?.ok_or_else(|| {
+        syn::Error::new(
             Span::call_site(),
             "No function using #[shuttle_runtime::main] found",
-        ));
-    };
-
-    // TODO: also parse function argument attributes (resources) and add to IR (re-use parsing code from codegen)
-    parse_infra_from_meta(&main_attr.meta)
+        )
+    })
 }
 
 /// Parses rust source code and looks for a function annotated with `#[shuttle_runtime::main]`.
@@ -53,7 +81,7 @@ fn parse_infra_from_meta(meta: &Meta) -> Result<Option<InfraRequest>, syn::Error
         // #[shuttle_runtime::main]
         Meta::Path(_) => Ok(None),
         // #[shuttle_runtime::main(...)]
-        Meta::List(ref meta_list) => parse_infra_from_meta_list(meta_list).map(Some),
+        Meta::List(ref meta_list) => parse_infra_from_meta_list(meta_list),
         // #[shuttle_runtime = ...]
         Meta::NameValue(_) => Err(syn::Error::new(
             meta.span(),
@@ -62,15 +90,33 @@ fn parse_infra_from_meta(meta: &Meta) -> Result<Option<InfraRequest>, syn::Error
     }
 }
 
-fn parse_infra_from_meta_list(meta_list: &MetaList) -> Result<InfraRequest, syn::Error> {
+fn parse_infra_from_meta_list(meta_list: &MetaList) -> Result<Option<InfraRequest>, syn::Error> {
     let mut infra_parser = InfraAttrParser::default();
     let meta_parser = parser(|meta| infra_parser.parse_nested_meta(meta));
     meta_parser.parse2(meta_list.tokens.clone())?;
-    Ok(infra_parser.into_infra())
+    // `tracing` alone does not declare any infra
+    let tracing_only = infra_parser.tracing.is_some() && !infra_parser.declares_infra;
+    let infra = infra_parser.into_infra()?;
+
+    Ok((!tracing_only).then_some(infra))
 }
 
+/// Upper bound of `replicas`, `min_replicas` and `max_replicas`
+pub const MAX_REPLICAS: u8 = 32;
+/// Upper bound of `shutdown_timeout`, in seconds
+pub const MAX_SHUTDOWN_TIMEOUT: u16 = 3600;
+
 #[derive(Default)]
-pub struct InfraAttrParser(InfraRequest);
+pub struct InfraAttrParser {
+    infra: InfraRequest,
+    /// Spans of the replica settings, to point at them when they conflict
+    replicas_span: Option<Span>,
+    max_replicas_span: Option<Span>,
+    /// Function customizing the tracing setup, which is not part of the infra
+    tracing: Option<Path>,
+    /// Whether any key other than `tracing` was given
+    declares_infra: bool,
+}
 impl InfraAttrParser {
     /// Parses one argument provided to the `#[shuttle_runtime::main(...)]` attribute macro.
     ///
@@ -78,13 +124,47 @@ impl InfraAttrParser {
     pub fn parse_nested_meta(&mut self, meta: ParseNestedMeta) -> Result<(), syn::Error> {
         let key = meta.path.require_ident()?.to_string();
         let value = meta.value()?;
+        self.declares_infra |= key != "tracing";
         match key.as_str() {
             "instance_size" => {
-                self.0.instance_size =
+                self.infra.instance_size =
                     Some(value.parse::<LitStr>()?.value().parse().map_err(|e| {
                         syn::Error::new(value.span(), format!("Invalid value: {e}"))
                     })?);
             }
+            "replicas" => {
+                let lit = value.parse::<LitInt>()?;
+                self.infra.replicas = Some(parse_in_range(&key, &lit, 1, MAX_REPLICAS)?);
+                self.replicas_span = Some(lit.span());
+            }
+            "min_replicas" => {
+                let lit = value.parse::<LitInt>()?;
+                self.infra.min_replicas = Some(parse_in_range(&key, &lit, 1, MAX_REPLICAS)?);
+            }
+            "max_replicas" => {
+                let lit = value.parse::<LitInt>()?;
+                self.infra.max_replicas = Some(parse_in_range(&key, &lit, 1, MAX_REPLICAS)?);
+                self.max_replicas_span = Some(lit.span());
+            }
+            "health_check_path" => {
+                let lit = value.parse::<LitStr>()?;
+                let path = lit.value();
+                if !path.starts_with('/') || path.contains(char::is_whitespace) {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "health_check_path should be a path starting with '/', such as \"/health\"",
+                    ));
+                }
+                self.infra.health_check_path = Some(path);
+            }
+            "shutdown_timeout" => {
+                let lit = value.parse::<LitInt>()?;
+                self.infra.shutdown_timeout =
+                    Some(parse_in_range(&key, &lit, 1, MAX_SHUTDOWN_TIMEOUT)?);
+            }
+            "tracing" => {
+                self.tracing = Some(value.parse()?);
+            }
             unknown_key => {
                 return Err(syn::Error::new(
                     key.span(),
@@ -94,11 +174,62 @@ impl InfraAttrParser {
         }
         Ok(())
     }
-    pub fn into_infra(self) -> InfraRequest {
-        self.0
+
+    /// Path of the function given with `tracing = ...`, used by codegen
+    pub fn tracing(&self) -> Option<&Path> {
+        self.tracing.as_ref()
+    }
+
+    /// Returns the parsed [`InfraRequest`], or an error if its settings conflict with each other
+    pub fn into_infra(self) -> Result<InfraRequest, syn::Error> {
+        let InfraRequest {
+            replicas,
+            min_replicas,
+            max_replicas,
+            ..
+        } = self.infra;
+        if let (Some(min), Some(max)) = (min_replicas, max_replicas) {
+            if min > max {
+                return Err(syn::Error::new(
+                    self.max_replicas_span.unwrap_or_else(Span::call_site),
+                    format!("max_replicas should be at least min_replicas ({min})"),
+                ));
+            }
+        }
+        if let Some(replicas) = replicas {
+            if min_replicas.is_some_and(|min| replicas < min)
+                || max_replicas.is_some_and(|max| replicas > max)
+            {
+                return Err(syn::Error::new(
+                    self.replicas_span.unwrap_or_else(Span::call_site),
+                    "replicas should be within min_replicas and max_replicas",
+                ));
+            }
+        }
+
+        Ok(self.infra)
     }
 }
 
+/// Parses an integer literal, failing with an error at the literal if it is not within `min..=max`
+fn parse_in_range<T>(key: &str, lit: &LitInt, min: T, max: T) -> Result<T, syn::Error>
+where
+    T: Copy + Display + Into<u64> + TryFrom<u64>,
+{
+    let out_of_range = || {
+        syn::Error::new(
+            lit.span(),
+            format!("{key} should be between {min} and {max}"),
+        )
+    };
+    let n = lit.base10_parse::<u64>().map_err(|_| out_of_range())?;
+    if n < min.into() || n > max.into() {
+        return Err(out_of_range());
+    }
+
+    T::try_from(n).map_err(|_| out_of_range())
+}
+
 #[cfg(test)]
 mod tests {
     use shuttle_common::models::project::ComputeTier;
@@ -238,4 +369,125 @@ mod tests {
             "Invalid macro attribute key: 'leet'"
         );
     }
+
+    #[test]
+    fn parse_resources() {
+        let rust = r#"
+        #[shuttle_runtime::main]
+        async fn main(#[shuttle_shared_db::Postgres] pool: PgPool) -> ShuttleAxum {}
+        "#;
+        assert_eq!(parse_infra_from_code(rust).unwrap(), None);
+        assert_eq!(
+            parse_resources_from_code(rust).unwrap()[0].to_attribute_string(),
+            "shuttle_shared_db::Postgres"
+        );
+
+        let rust = r#"
+        #[shuttle_runtime::main(instance_size = "xs")]
+        async fn main(#[shuttle_shared_db::Postgres] pool: PgPool) -> ShuttleAxum {}
+        "#;
+        let infra = parse_infra_from_code(rust).unwrap().unwrap();
+        assert_eq!(infra.instance_size, Some(ComputeTier::XS));
+        assert_eq!(
+            infra.resources.unwrap()[0].to_attribute_string(),
+            "shuttle_shared_db::Postgres"
+        );
+
+        let rust = r#"
+        #[shuttle_runtime::main]
+        async fn main() -> ShuttleAxum {}
+        "#;
+        assert_eq!(parse_infra_from_code(rust).unwrap(), None);
+    }
+
+    #[test]
+    fn parse_scaling() {
+        let rust = r#"
+        #[shuttle_runtime::main(
+            instance_size = "s",
+            replicas = 2,
+            min_replicas = 1,
+            max_replicas = 4,
+            health_check_path = "/health",
+            shutdown_timeout = 30,
+        )]
+        async fn main() -> ShuttleAxum {}
+        "#;
+        assert_eq!(
+            parse_infra_from_code(rust).unwrap().unwrap(),
+            InfraRequest {
+                instance_size: Some(ComputeTier::S),
+                replicas: Some(2),
+                health_check_path: Some("/health".to_owned()),
+                min_replicas: Some(1),
+                max_replicas: Some(4),
+                shutdown_timeout: Some(30),
+                ..Default::default()
+            }
+        );
+
+        let error = |attr: &str| {
+            parse_infra_from_code(&format!(
+                "#[shuttle_runtime::main({attr})] async fn main() -> ShuttleAxum {{}}"
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+        assert_eq!(error("replicas = 0"), "replicas should be between 1 and 32");
+        assert_eq!(
+            error("max_replicas = 1000"),
+            "max_replicas should be between 1 and 32"
+        );
+        assert_eq!(
+            error("shutdown_timeout = 0"),
+            "shutdown_timeout should be between 1 and 3600"
+        );
+        assert_eq!(error("replicas = \"2\""), "expected integer literal");
+        assert_eq!(
+            error("health_check_path = \"health\""),
+            "health_check_path should be a path starting with '/', such as \"/health\""
+        );
+        assert_eq!(
+            error("min_replicas = 4, max_replicas = 2"),
+            "max_replicas should be at least min_replicas (4)"
+        );
+        assert_eq!(
+            error("replicas = 8, max_replicas = 4"),
+            "replicas should be within min_replicas and max_replicas"
+        );
+    }
+
+    #[test]
+    fn parse_tracing() {
+        let rust = r#"
+        #[shuttle_runtime::main(tracing = telemetry::setup, replicas = 2)]
+        async fn main() -> ShuttleAxum {}
+        "#;
+        assert_eq!(
+            parse_infra_from_code(rust).unwrap().unwrap(),
+            InfraRequest {
+                replicas: Some(2),
+                ..Default::default()
+            }
+        );
+
+        let rust = r#"
+        #[shuttle_runtime::main(tracing = telemetry::setup)]
+        async fn main() -> ShuttleAxum {}
+        "#;
+        assert_eq!(parse_infra_from_code(rust).unwrap(), None);
+
+        let mut infra_parser = InfraAttrParser::default();
+        parser(|meta| infra_parser.parse_nested_meta(meta))
+            .parse2(quote::quote!(tracing = telemetry::setup))
+            .unwrap();
+        assert_eq!(
+            infra_parser.tracing(),
+            Some(&parse_quote!(telemetry::setup))
+        );
+        assert!(parse_infra_from_code(
+            "#[shuttle_runtime::main(tracing = \"setup\")] async fn main() -> ShuttleAxum {}"
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,175 @@
+use quote::ToTokens;
+use shuttle_common::models::infra::{ResourceDeclaration, ResourceOption};
+use syn::{
+    parse::Parse, punctuated::Punctuated, spanned::Spanned, Attribute, Expr, FnArg, Ident, ItemFn,
+    Pat, Path, Token,
+};
+
+/// Represents a builder pattern that a resource input gets turned into
+#[derive(Debug, PartialEq)]
+pub struct BuilderPattern {
+    /// Namespace path to the builder
+    pub path: Path,
+    /// Options to call the builder with
+    pub options: BuilderOptions,
+}
+
+/// The options (function names, values) to call the builder pattern with
+#[derive(Debug, Default, PartialEq)]
+pub struct BuilderOptions {
+    pub options: Punctuated<BuilderOption, Token![,]>,
+}
+
+/// One item in the builder pattern
+#[derive(Debug, PartialEq)]
+pub struct BuilderOption {
+    /// Identifier of the option to set
+    pub ident: Ident,
+    /// Value to set option to
+    pub value: Expr,
+}
+
+impl Parse for BuilderOptions {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        Ok(Self {
+            options: input.parse_terminated(BuilderOption::parse, Token![,])?,
+        })
+    }
+}
+
+impl Parse for BuilderOption {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let _equal: Token![=] = input.parse()?;
+        let value = input.parse()?;
+
+        Ok(Self { ident, value })
+    }
+}
+
+impl BuilderPattern {
+    /// Parses the builder from the resource attribute on an argument of the main function.
+    /// A plain attribute without a list, like `#[shuttle_shared_db::Postgres]`, has no options.
+    pub fn from_attribute(attr: &Attribute) -> syn::Result<Self> {
+        let options = if attr.meta.require_list().is_err() {
+            Default::default()
+        } else {
+            attr.parse_args()?
+        };
+
+        Ok(Self {
+            path: attr.path().clone(),
+            options,
+        })
+    }
+
+    pub fn to_declaration(&self, argument: &Ident) -> ResourceDeclaration {
+        ResourceDeclaration {
+            path: tokens_to_string(&self.path),
+            argument: argument.to_string(),
+            options: self
+                .options
+                .options
+                .iter()
+                .map(|o| ResourceOption {
+                    name: o.ident.to_string(),
+                    value: tokens_to_string(&o.value),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Source form of a path or expression as written.
+/// Tokens without a source location, such as ones created by a macro, are printed as a token stream instead.
+fn tokens_to_string(tokens: &impl ToTokens) -> String {
+    tokens
+        .span()
+        .source_text()
+        .unwrap_or_else(|| tokens.to_token_stream().to_string())
+}
+
+/// Parses the resources declared with attributes on the arguments of the main function.
+/// Arguments without an attribute are skipped, codegen reports those.
+pub fn parse_resources_from_fn(item_fn: &ItemFn) -> syn::Result<Vec<ResourceDeclaration>> {
+    item_fn
+        .sig
+        .inputs
+        .iter()
+        .filter_map(|input| match input {
+            FnArg::Receiver(_) => None,
+            FnArg::Typed(typed) => Some(typed),
+        })
+        .filter_map(|typed| match (typed.pat.as_ref(), typed.attrs.first()) {
+            (Pat::Ident(pat_ident), Some(attr)) => Some((&pat_ident.ident, attr)),
+            _ => None,
+        })
+        .map(|(ident, attr)| Ok(BuilderPattern::from_attribute(attr)?.to_declaration(ident)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn parse_builder_options() {
+        let input: BuilderOptions = parse_quote!(
+            string = "string_val",
+            boolean = true,
+            integer = 5,
+            float = 2.65,
+            enum_variant = SomeEnum::Variant1,
+            sensitive = "user:{secrets.password}"
+        );
+
+        let mut expected: BuilderOptions = Default::default();
+        expected.options.push(parse_quote!(string = "string_val"));
+        expected.options.push(parse_quote!(boolean = true));
+        expected.options.push(parse_quote!(integer = 5));
+        expected.options.push(parse_quote!(float = 2.65));
+        expected
+            .options
+            .push(parse_quote!(enum_variant = SomeEnum::Variant1));
+        expected
+            .options
+            .push(parse_quote!(sensitive = "user:{secrets.password}"));
+
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    fn resources() {
+        let item_fn: ItemFn = syn::parse_str(
+            r#"
+            async fn main(
+                #[shuttle_shared_db::Postgres(
+                    local_uri = "postgres://{secrets.PASSWORD}@localhost"
+                )]
+                pool: PgPool,
+                #[shuttle_runtime::Secrets] secrets: SecretStore,
+                #[aws::Rds(size = Size::Large, public = false, tags = [a.b(), c . d])] rds: RdsPool,
+                not_a_resource: u8,
+            ) -> ShuttleAxum {
+            }
+            "#,
+        )
+        .unwrap();
+        let resources = parse_resources_from_fn(&item_fn).unwrap();
+        assert_eq!(
+            resources
+                .iter()
+                .map(ResourceDeclaration::to_attribute_string)
+                .collect::<Vec<_>>(),
+            [
+                r#"shuttle_shared_db::Postgres(local_uri = "postgres://{secrets.PASSWORD}@localhost")"#,
+                "shuttle_runtime::Secrets",
+                "aws::Rds(size = Size::Large, public = false, tags = [a.b(), c . d])",
+            ]
+        );
+        assert_eq!(resources[0].argument, "pool");
+        assert_eq!(resources[2].options[0].name, "size");
+    }
+}
@@ -9,6 +9,9 @@ mod plugins;
 /// shuttle.dev runtime
 mod rt;
 mod start;
+/// Configurable tracing subscriber
+#[cfg(feature = "setup-tracing")]
+mod subscriber;
 
 #[cfg(feature = "setup-otel-exporter")]
 mod telemetry;
@@ -22,15 +25,43 @@ pub use shuttle_service::{
     CustomError, DbInput, DeploymentMetadata, Environment, Error, IntoResource, ResourceFactory,
     ResourceInputBuilder, SecretStore, Service,
 };
+#[cfg(feature = "setup-otel-exporter")]
+pub use subscriber::Sampler;
+#[cfg(feature = "setup-tracing")]
+pub use subscriber::{TracingConfig, TracingSubscriber};
 pub use tokio;
 
 const VERSION_STRING: &str = concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION"));
 
+/// Used by the codegen for `#[shuttle_runtime::main(tracing = ...)]`
+#[cfg(feature = "setup-tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __start_with_tracing {
+    ($($arg:expr),* $(,)?) => {
+        $crate::__internals::start_with_tracing($($arg),*)
+    };
+}
+
+/// Used by the codegen for `#[shuttle_runtime::main(tracing = ...)]`, pointing the error at the tracing function
+#[cfg(not(feature = "setup-tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __start_with_tracing {
+    ($($arg:tt)*) => {
+        ::core::compile_error!(
+            "`tracing = ...` in #[shuttle_runtime::main] requires the `setup-tracing` feature of shuttle-runtime"
+        )
+    };
+}
+
 // Not part of public API
 #[doc(hidden)]
 pub mod __internals {
     // Internals used by the codegen
     pub use crate::start::start;
+    #[cfg(feature = "setup-tracing")]
+    pub use crate::start::start_with_tracing;
 
     // Dependencies required by the codegen
     pub use anyhow::Context;
@@ -17,16 +17,13 @@ fn initial_args_and_env_check() -> anyhow::Result<()> {
     Ok(())
 }
 
-pub async fn start(
-    loader: impl Loader + Send + 'static,
-    runner: impl Runner + Send + 'static,
-    crate_name: &'static str,
-    package_version: &'static str,
-) {
+/// Prints the version if requested, and checks the args and env.
+/// Returns false if the runtime should not start.
+fn version_and_startup_checks(crate_name: &'static str, package_version: &'static str) -> bool {
     // `--version` overrides any other arguments. Used by cargo-shuttle to check compatibility on local runs.
     if std::env::args().any(|arg| arg == "--version") {
         println!("{}", crate::VERSION_STRING);
-        return;
+        return false;
     }
 
     println!(
@@ -41,36 +38,47 @@ pub async fn start(
         let help_str = "[HINT]: Run your Shuttle app with `shuttle run`";
         let wrapper_str = "-".repeat(help_str.len());
         eprintln!("{wrapper_str}\n{help_str}\n{wrapper_str}");
+        return false;
+    }
+
+    true
+}
+
+pub async fn start(
+    loader: impl Loader + Send + 'static,
+    runner: impl Runner + Send + 'static,
+    crate_name: &'static str,
+    package_version: &'static str,
+) {
+    if !version_and_startup_checks(crate_name, package_version) {
         return;
     }
 
     // this is handled after arg parsing to not interfere with --version above
-    #[cfg(all(feature = "setup-tracing", not(feature = "setup-otel-exporter")))]
-    {
-        use tracing_subscriber::{fmt, prelude::*, registry, EnvFilter};
-        registry()
-            .with(fmt::layer().without_time())
-            .with(
-                // let user override RUST_LOG in local run if they want to
-                EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                    // otherwise use our default
-                    format!("info,{}=debug", crate_name).into()
-                }),
-            )
-            .init();
-        tracing::warn!(
-            "Default tracing subscriber initialized (https://docs.shuttle.dev/docs/logs)"
-        );
+    #[cfg(feature = "setup-tracing")]
+    let _guard = crate::TracingConfig::new(crate_name, package_version).init();
+
+    let exit_code = rt::start(loader, runner).await;
+
+    // TODO: drop/shutdown logger guards
+
+    std::process::exit(exit_code)
+}
+
+/// Like [`start`], with the tracing setup customized by the function given with `#[shuttle_runtime::main(tracing = ...)]`
+#[cfg(feature = "setup-tracing")]
+pub async fn start_with_tracing(
+    loader: impl Loader + Send + 'static,
+    runner: impl Runner + Send + 'static,
+    crate_name: &'static str,
+    package_version: &'static str,
+    tracing: impl FnOnce(crate::TracingConfig) -> crate::TracingConfig,
+) {
+    if !version_and_startup_checks(crate_name, package_version) {
+        return;
     }
 
-    #[cfg(feature = "setup-otel-exporter")]
-    let _guard = {
-        let guard = crate::telemetry::init_tracing_subscriber(crate_name, package_version);
-        tracing::warn!(
-            "Default tracing subscriber with otel exporter initialized (https://docs.shuttle.dev/docs/telemetry)"
-        );
-        guard
-    };
+    let _guard = tracing(crate::TracingConfig::new(crate_name, package_version)).init();
 
     let exit_code = rt::start(loader, runner).await;
 
@@ -0,0 +1,132 @@
+use tracing_subscriber::{fmt, layer::Layered, prelude::*, registry, EnvFilter, Layer, Registry};
+
+#[cfg(feature = "setup-otel-exporter")]
+pub use opentelemetry_sdk::trace::Sampler;
+
+/// The subscriber that the layers of a [`TracingConfig`] are added to
+pub type TracingSubscriber = Layered<EnvFilter, Registry>;
+
+pub(crate) type BoxedLayer = Box<dyn Layer<TracingSubscriber> + Send + Sync>;
+
+/// Configuration of the tracing subscriber that the runtime installs.
+///
+/// A function given with `#[shuttle_runtime::main(tracing = ...)]` receives the default configuration
+/// and can add layers, replace the filter or the default fmt layer, and set a sampler.
+/// With the `setup-otel-exporter` feature, the Shuttle exporters and resource attributes are always kept.
+pub struct TracingConfig {
+    crate_name: &'static str,
+    #[cfg_attr(not(feature = "setup-otel-exporter"), allow(dead_code))]
+    package_version: &'static str,
+    filter: Option<EnvFilter>,
+    default_fmt: bool,
+    layers: Vec<BoxedLayer>,
+    #[cfg(feature = "setup-otel-exporter")]
+    sampler: Option<Sampler>,
+}
+
+impl TracingConfig {
+    pub(crate) fn new(crate_name: &'static str, package_version: &'static str) -> Self {
+        Self {
+            crate_name,
+            package_version,
+            filter: None,
+            default_fmt: true,
+            layers: Vec::new(),
+            #[cfg(feature = "setup-otel-exporter")]
+            sampler: None,
+        }
+    }
+
+    /// Replaces the default filter, which is read from `RUST_LOG` or else is `info,<crate name>=debug`
+    pub fn with_filter(mut self, filter: EnvFilter) -> Self {
+        self.filter = Some(filter);
+        self
+    }
+
+    /// Removes the default fmt layer that prints to stdout, such as when adding a JSON formatted one instead
+    pub fn without_default_fmt(mut self) -> Self {
+        self.default_fmt = false;
+        self
+    }
+
+    /// Adds a layer to the subscriber. Use [`Layer::with_filter`] to filter only this layer.
+    pub fn with_layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<TracingSubscriber> + Send + Sync + 'static,
+    {
+        self.layers.push(layer.boxed());
+        self
+    }
+
+    /// Sets the sampler of the exported traces, which by default exports all of them
+    #[cfg(feature = "setup-otel-exporter")]
+    pub fn with_sampler(mut self, sampler: Sampler) -> Self {
+        self.sampler = Some(sampler);
+        self
+    }
+
+    /// Installs the subscriber as the global default
+    pub(crate) fn init(self) -> TracingGuard {
+        let filter = self.filter.unwrap_or_else(|| {
+            // let user override RUST_LOG in local run if they want to
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+                // otherwise use our default
+                format!("info,{}=debug", self.crate_name).into()
+            })
+        });
+
+        let mut layers: Vec<BoxedLayer> = Vec::new();
+        if self.default_fmt {
+            #[cfg(not(feature = "setup-otel-exporter"))]
+            layers.push(fmt::layer().without_time().boxed());
+            #[cfg(feature = "setup-otel-exporter")]
+            layers.push(
+                fmt::layer()
+                    .compact()
+                    .with_level(true)
+                    .with_target(true)
+                    .boxed(),
+            );
+        }
+
+        #[cfg(feature = "setup-otel-exporter")]
+        let providers = {
+            let (otel_layers, providers) =
+                crate::telemetry::layers(self.crate_name, self.package_version, self.sampler);
+            layers.extend(otel_layers);
+            providers
+        };
+
+        layers.extend(self.layers);
+        registry().with(filter).with(layers).init();
+
+        #[cfg(not(feature = "setup-otel-exporter"))]
+        tracing::warn!(
+            "Default tracing subscriber initialized (https://docs.shuttle.dev/docs/logs)"
+        );
+        #[cfg(feature = "setup-otel-exporter")]
+        {
+            if std::env::var(opentelemetry_otlp::OTEL_EXPORTER_OTLP_ENDPOINT).is_err() {
+                tracing::warn!(
+                    "No value set for `{}` env var, \
+                    declining to attach OTLP exporter to default tracing subscriber",
+                    opentelemetry_otlp::OTEL_EXPORTER_OTLP_ENDPOINT,
+                );
+            }
+            tracing::warn!(
+                "Default tracing subscriber with otel exporter initialized (https://docs.shuttle.dev/docs/telemetry)"
+            );
+        }
+
+        TracingGuard {
+            #[cfg(feature = "setup-otel-exporter")]
+            _providers: providers,
+        }
+    }
+}
+
+/// Shuts down the telemetry providers when dropped
+pub(crate) struct TracingGuard {
+    #[cfg(feature = "setup-otel-exporter")]
+    _providers: crate::telemetry::ProviderGuard,
+}
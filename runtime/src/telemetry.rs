@@ -18,7 +18,7 @@ use opentelemetry_sdk::{
     propagation::TraceContextPropagator,
     resource::{Resource, ResourceDetector, TelemetryResourceDetector},
     runtime,
-    trace::TracerProvider,
+    trace::{Sampler, TracerProvider},
 };
 use opentelemetry_semantic_conventions::{
     attribute::{CODE_FILEPATH, CODE_LINENO, SERVICE_NAME, SERVICE_VERSION},
@@ -31,12 +31,9 @@ use tracing_core::{
 };
 use tracing_log::AsLog;
 use tracing_opentelemetry::{MetricsLayer, OpenTelemetryLayer, OtelData};
-use tracing_subscriber::{
-    layer::{Context, SubscriberExt},
-    registry::LookupSpan,
-    util::SubscriberInitExt,
-    EnvFilter, Layer,
-};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+use crate::subscriber::BoxedLayer;
 
 #[derive(Clone, Debug)]
 pub struct ProviderGuard {
@@ -438,15 +435,16 @@ pub fn init_meter_provider(endpoint: &Option<String>, resource: Resource) -> Sdk
 }
 
 // Construct TracerProvider for OpenTelemetryLayer
-pub fn init_tracer_provider(endpoint: &Option<String>, resource: Resource) -> TracerProvider {
-    // TODO(the-wondersmith): make trace sample rate & strategy configurable
-    // let sampler = opentelemetry_sdk::trace::Sampler::ParentBased(Box::new(
-    //     opentelemetry_sdk::trace::Sampler::TraceIdRatioBased(1.0),
-    // ));
-
-    let mut builder = TracerProvider::builder()
-        // .with_sampler(sampler)
-        .with_resource(resource);
+pub fn init_tracer_provider(
+    endpoint: &Option<String>,
+    resource: Resource,
+    sampler: Option<Sampler>,
+) -> TracerProvider {
+    let mut builder = TracerProvider::builder().with_resource(resource);
+
+    if let Some(sampler) = sampler {
+        builder = builder.with_sampler(sampler);
+    }
 
     if let Some(endpoint) = endpoint {
         let exporter = opentelemetry_otlp::SpanExporter::builder()
@@ -465,11 +463,12 @@ pub fn init_tracer_provider(endpoint: &Option<String>, resource: Resource) -> Tr
     provider
 }
 
-// Initialize tracing-subscriber and return ExporterGuard for opentelemetry-related termination processing
-pub fn init_tracing_subscriber(
+// Construct the layers exporting to the OTLP endpoint, and the ProviderGuard for opentelemetry-related termination processing
+pub fn layers(
     crate_name: &'static str,
     package_version: &'static str,
-) -> ProviderGuard {
+    sampler: Option<Sampler>,
+) -> (Vec<BoxedLayer>, ProviderGuard) {
     global::set_text_map_propagator(TraceContextPropagator::new());
 
     let resource = resource(crate_name, package_version);
@@ -477,37 +476,21 @@ pub fn init_tracing_subscriber(
     // The OTLP_HOST env var is useful for setting a specific host when running locally
     let endpoint = std::env::var(OTEL_EXPORTER_OTLP_ENDPOINT).ok();
 
-    let tracer = init_tracer_provider(&endpoint, resource.clone());
+    let tracer = init_tracer_provider(&endpoint, resource.clone(), sampler);
     let meter = init_meter_provider(&endpoint, resource.clone());
     let logger = init_log_subscriber(&endpoint, resource);
 
-    let level_filter =
-        std::env::var("RUST_LOG").unwrap_or_else(|_| format!("info,{}=debug", crate_name));
-
-    let layers = EnvFilter::from(&level_filter)
-        .and_then(MetricsLayer::new(meter.clone()))
-        .and_then(OpenTelemetryLayer::new(tracer.tracer("shuttle-telemetry")))
-        .and_then(
-            tracing_subscriber::fmt::layer()
-                .compact()
-                .with_level(true)
-                .with_target(true),
-        )
-        .and_then(LogCourier::new(logger.logger("shuttle-telemetry")));
-
-    tracing_subscriber::registry().with(layers).init();
-
-    if endpoint.is_none() {
-        tracing::warn!(
-            "No value set for `{}` env var, \
-            declining to attach OTLP exporter to default tracing subscriber",
-            OTEL_EXPORTER_OTLP_ENDPOINT,
-        );
-    }
+    let layers = vec![
+        MetricsLayer::new(meter.clone()).boxed(),
+        OpenTelemetryLayer::new(tracer.tracer("shuttle-telemetry")).boxed(),
+        LogCourier::new(logger.logger("shuttle-telemetry")).boxed(),
+    ];
 
-    ProviderGuard {
+    let guard = ProviderGuard {
         logger,
         tracer,
         meter,
-    }
+    };
+
+    (layers, guard)
 }